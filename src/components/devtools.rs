@@ -0,0 +1,66 @@
+use crate::guard::RouterGuardContext;
+use crate::router::use_router;
+use crate::target::Target;
+use yew::prelude::*;
+
+/// Properties for the [`RouterDevTools`] component.
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct RouterDevToolsProps {
+    /// Show the panel initially, instead of only the toggle button.
+    #[prop_or_default]
+    pub open: bool,
+}
+
+const PANEL_STYLE: &str = "position: fixed; bottom: 0.5em; right: 0.5em; z-index: 10000; \
+    max-width: 40em; max-height: 50vh; overflow: auto; padding: 0.5em; \
+    font-family: monospace; font-size: 0.8em; background: #fff; color: #000; \
+    border: 1px solid #888; opacity: 0.9;";
+
+/// A floating panel, showing the state of the router, for debugging.
+///
+/// The panel is rendered at the level of the document body, and shows the active and pending
+/// target, the history entries, and the number of guards. It is only available in debug builds.
+#[function_component(RouterDevTools)]
+pub fn router_dev_tools<T>(props: &RouterDevToolsProps) -> Html
+where
+    T: Target + 'static,
+{
+    let router = use_router::<T>().expect("Need Router or Nested component");
+    let guards = use_context::<RouterGuardContext<T>>().unwrap_or_default();
+
+    let open = use_state_eq(|| props.open);
+    let ontoggle = {
+        let open = open.clone();
+        Callback::from(move |_| open.set(!*open))
+    };
+
+    let content = match *open {
+        true => html!(
+            <dl>
+                <dt>{ "Active" }</dt>
+                <dd>{ format!("{:?}", router.active()) }</dd>
+                <dt>{ "Pending" }</dt>
+                <dd>{ format!("{:?}", router.pending()) }</dd>
+                <dt>{ "History" }</dt>
+                <dd>
+                    <ol>
+                        { for router.serialize_history().into_iter().map(|entry| html!(<li>{ entry }</li>)) }
+                    </ol>
+                </dd>
+                <dt>{ "Guards" }</dt>
+                <dd>{ guards.len() }</dd>
+            </dl>
+        ),
+        false => html!(),
+    };
+
+    create_portal(
+        html!(
+            <div style={PANEL_STYLE}>
+                <button onclick={ontoggle}>{ "Router" }</button>
+                { content }
+            </div>
+        ),
+        gloo_utils::body().into(),
+    )
+}
@@ -0,0 +1,28 @@
+use yew::prelude::*;
+
+/// Properties for the [`ExternalLink`] component.
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct ExternalLinkProps {
+    /// It's children, rendered inside the element.
+    pub children: Children,
+
+    /// The URL to link to.
+    pub href: AttrValue,
+
+    /// CSS classes of the element.
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// A link to an external URL, outside of the application, like `https://example.com`.
+///
+/// In contrast to the [`super::Link`] component, this doesn't navigate using the router, but
+/// renders a plain `<a>` element. An external link is never considered active.
+#[function_component(ExternalLink)]
+pub fn external_link(props: &ExternalLinkProps) -> Html {
+    html!(
+        <a class={props.class.clone()} href={props.href.clone()}>
+            { for props.children.iter() }
+        </a>
+    )
+}
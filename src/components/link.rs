@@ -35,6 +35,11 @@ where
     /// CSS classes which are added when the target is not the active route.
     #[prop_or_default]
     pub inactive: Classes,
+
+    /// Set the `aria-current` attribute, to `page` if the target is the active route, or to
+    /// `true` if the link is active otherwise (e.g. using a predicate).
+    #[prop_or(true)]
+    pub aria_current: bool,
 }
 
 mod default {
@@ -44,6 +49,11 @@ mod default {
 }
 
 /// A link component, navigating to a [`Target`] on the `onclick` event.
+///
+/// When rendered as `<a>` element, the link also gets the URL of the target as `href` attribute.
+/// That way, it still works without JavaScript, and can be opened in a new tab or window.
+///
+/// For linking to an external URL, use the [`super::ExternalLink`] component.
 #[function_component(Link)]
 pub fn link<T>(props: &LinkProps<T>) -> Html
 where
@@ -53,6 +63,8 @@ where
 
     let mut classes = props.class.clone();
 
+    let same = router.is_same(&props.target);
+
     let active = match props.any {
         true => {
             let active = router.active();
@@ -64,21 +76,34 @@ where
                 .clone()
                 .map(|t| predicate.emit(t))
                 .unwrap_or(false),
-            None => router.is_same(&props.target),
+            None => same,
         },
     };
 
+    let aria_current = match (props.aria_current, same, active) {
+        (false, _, _) => None,
+        (true, true, _) => Some(AttrValue::from("page")),
+        (true, false, true) => Some(AttrValue::from("true")),
+        (true, false, false) => None,
+    };
+
     match active {
         true => classes.extend(props.active.clone()),
         false => classes.extend(props.inactive.clone()),
     }
 
-    let target = props.target.clone();
-    let onclick = Callback::from(move |_| router.push(target.clone()));
+    let link = router.get_link_props(&props.target);
+    let href = match props.element.as_str() {
+        "a" => Some(AttrValue::from(link.href)),
+        _ => None,
+    };
+    let onclick = link.onclick;
 
     html!(
         <@{props.element.clone()}
             class={classes}
+            {href}
+            aria-current={aria_current}
             {onclick}
         >
             { for props.children.iter() }
@@ -1,7 +1,13 @@
 //! Some out-of-the box components.
 
 mod active;
+#[cfg(debug_assertions)]
+mod devtools;
+mod external_link;
 mod link;
 
 pub use active::*;
+#[cfg(debug_assertions)]
+pub use devtools::*;
+pub use external_link::*;
 pub use link::*;
@@ -0,0 +1,35 @@
+use yew::prelude::*;
+
+/// A routing context, which is not bound to a target type.
+///
+/// This allows generic components, like analytics or debugging tools, to work with any router,
+/// based on the path segments only.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErasedRouterContext {
+    pub(crate) active_path: Option<Vec<String>>,
+    pub(crate) navigate: Callback<Vec<String>>,
+}
+
+impl ErasedRouterContext {
+    /// Get the path segments of the active target, this may be [`None`], in the case there is no
+    /// active target.
+    pub fn active_path(&self) -> Option<&[String]> {
+        self.active_path.as_deref()
+    }
+
+    /// Push a new state to the history, parsing the target from the provided path segments.
+    ///
+    /// If the path doesn't match a target, this does nothing.
+    pub fn push(&self, path: Vec<String>) {
+        self.navigate.emit(path);
+    }
+}
+
+#[hook]
+/// Get access to the type-erased context of the top-level router.
+///
+/// The hook requires to be called from a component which is nested into a [`crate::Router`]
+/// component, of any type. If not, it will return [`None`].
+pub fn use_erased_router() -> Option<ErasedRouterContext> {
+    use_context()
+}
@@ -0,0 +1,60 @@
+//! Guarding navigation
+
+use crate::target::Target;
+use yew::prelude::*;
+
+/// Guards, checking if a navigation to a target is allowed.
+///
+/// This can be provided as a context above the [`crate::Router`] component, using a
+/// [`ContextProvider`]. This allows application wide logic, like authentication, to prevent
+/// navigating to a target, without passing properties down to the router.
+///
+/// Navigation is only allowed if all guards allow it. Guards are only checked when navigating
+/// using the router, not when the location is changed by the browser.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouterGuardContext<T>
+where
+    T: Target,
+{
+    guards: Vec<Callback<T, bool>>,
+}
+
+impl<T> Default for RouterGuardContext<T>
+where
+    T: Target,
+{
+    fn default() -> Self {
+        Self { guards: vec![] }
+    }
+}
+
+impl<T> RouterGuardContext<T>
+where
+    T: Target,
+{
+    /// Create a new context, without any guards.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a guard, which returns `true` if navigating to the target is allowed.
+    pub fn with_guard(mut self, guard: impl Into<Callback<T, bool>>) -> Self {
+        self.guards.push(guard.into());
+        self
+    }
+
+    /// The number of guards.
+    pub fn len(&self) -> usize {
+        self.guards.len()
+    }
+
+    /// Check if there are no guards.
+    pub fn is_empty(&self) -> bool {
+        self.guards.is_empty()
+    }
+
+    /// Check if navigating to the target is allowed by all guards.
+    pub fn allows(&self, target: &T) -> bool {
+        self.guards.iter().all(|guard| guard.emit(target.clone()))
+    }
+}
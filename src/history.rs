@@ -0,0 +1,527 @@
+use crate::router::RouterMode;
+use gloo_history::{AnyHistory, BrowserHistory, HashHistory, History, HistoryListener};
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::rc::{Rc, Weak};
+use wasm_bindgen::JsValue;
+
+/// The history of a router.
+///
+/// This wraps the history of the browser, or an in-memory history. The in-memory history is
+/// implemented here, as we need access to its entries.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum RouterHistory {
+    Browser(AnyHistory),
+    Memory(MemoryHistory),
+}
+
+/// A handle to a listener registered with [`RouterHistory::listen`]. Dropping it, unregisters
+/// the listener.
+pub(crate) enum RouterListener {
+    Browser { _listener: HistoryListener },
+    Memory { _listener: Rc<dyn Fn()> },
+}
+
+impl RouterHistory {
+    /// Create a new history for the mode.
+    ///
+    /// The maximum length and the key only apply to the in-memory history, the browser controls
+    /// the length of its history, and there is only one. An in-memory history with a key is
+    /// shared with all routers using the same key, including ones created later.
+    pub(crate) fn new(mode: &RouterMode, max_length: Option<usize>, key: Option<&str>) -> Self {
+        match mode {
+            RouterMode::Browser => Self::Browser(AnyHistory::Browser(BrowserHistory::new())),
+            RouterMode::Hash => Self::Browser(AnyHistory::Hash(HashHistory::new())),
+            RouterMode::Memory(entries) => match key {
+                Some(key) => Self::shared(key, entries, max_length),
+                None => Self::Memory(MemoryHistory::with_entries(entries, max_length)),
+            },
+        }
+    }
+
+    /// Get the in-memory history shared using the key, creating it if it doesn't exist yet.
+    pub(crate) fn shared(key: &str, entries: &[String], max_length: Option<usize>) -> Self {
+        Self::Memory(MEMORY_HISTORIES.with(|histories| {
+            histories
+                .borrow_mut()
+                .entry(key.to_string())
+                .or_insert_with(|| MemoryHistory::with_entries(entries, max_length))
+                .clone()
+        }))
+    }
+
+    /// Forget the in-memory history shared using the key.
+    ///
+    /// Existing users keep their history, while the next one gets a new history created.
+    pub(crate) fn remove_shared(key: &str) {
+        MEMORY_HISTORIES.with(|histories| histories.borrow_mut().remove(key));
+    }
+
+    /// Get the current (non-local) path.
+    pub(crate) fn path(&self) -> String {
+        match self {
+            Self::Browser(AnyHistory::Hash(_)) => hash_location().path,
+            Self::Browser(history) => history.location().path().to_string(),
+            Self::Memory(history) => history.current(|entry| entry.path.clone()),
+        }
+    }
+
+    /// Get the current route, including the query and fragment.
+    pub(crate) fn route(&self) -> String {
+        match self {
+            Self::Browser(AnyHistory::Hash(_)) => hash_location().route(),
+            Self::Browser(history) => {
+                let location = history.location();
+                format!(
+                    "{}{}{}",
+                    location.path(),
+                    location.query_str(),
+                    location.hash()
+                )
+            }
+            Self::Memory(history) => history.current(MemoryEntry::route),
+        }
+    }
+
+    /// Get the current query string, including the leading `?`, if there is one.
+    pub(crate) fn query(&self) -> String {
+        match self {
+            Self::Browser(AnyHistory::Hash(_)) => hash_location().query,
+            Self::Browser(history) => history.location().query_str().to_string(),
+            Self::Memory(history) => history.current(|entry| entry.query.clone()),
+        }
+    }
+
+    /// Get the URL to use for linking to a route.
+    pub(crate) fn href(&self, route: String) -> String {
+        match self {
+            Self::Browser(AnyHistory::Hash(_)) => format!("#{route}"),
+            _ => route,
+        }
+    }
+
+    pub(crate) fn push(&self, route: String) {
+        match self {
+            Self::Browser(history) => history.push(route),
+            Self::Memory(history) => history.push(MemoryEntry::parse(&route)),
+        }
+    }
+
+    /// Push a new entry, with the query string (including the leading `?`, if there is one).
+    pub(crate) fn push_with_query(&self, route: String, query: &str) {
+        match self {
+            Self::Browser(AnyHistory::Hash(history)) => {
+                // the hash history doesn't accept a query as part of the route
+                if let Err(err) = history.push_with_query(route, decode_query(query)) {
+                    log::warn!("Failed to push with query: {err}");
+                }
+            }
+            _ => self.push(format!("{route}{query}")),
+        }
+    }
+
+    pub(crate) fn replace(&self, route: String) {
+        match self {
+            Self::Browser(history) => history.replace(route),
+            Self::Memory(history) => history.replace(MemoryEntry::parse(&route)),
+        }
+    }
+
+    pub(crate) fn replace_query(&self, query: HashMap<String, String>) {
+        match self {
+            Self::Browser(AnyHistory::Hash(history)) => {
+                if let Err(err) = history.replace_with_query(hash_location().path, query) {
+                    log::warn!("Failed to replace query: {err}");
+                }
+            }
+            Self::Browser(history) => {
+                let location = history.location();
+                if let Err(err) = history.replace_with_query(location.path(), query) {
+                    log::warn!("Failed to replace query: {err}");
+                }
+            }
+            Self::Memory(history) => history.update(|entry| {
+                entry.query = encode_query(query);
+            }),
+        }
+    }
+
+    pub(crate) fn replace_state(&self, state: JsValue) {
+        match self {
+            Self::Browser(AnyHistory::Hash(history)) => {
+                // the hash history doesn't accept a query as part of the route
+                let location = hash_location();
+                let query = decode_query(&location.query);
+                if let Err(err) = history.replace_with_query_and_state(location.path, query, state)
+                {
+                    log::warn!("Failed to replace state: {err}");
+                }
+            }
+            Self::Browser(history) => history.replace_with_state(self.route(), state),
+            Self::Memory(history) => history.update(|entry| {
+                entry.state = Some(Rc::new(state));
+            }),
+        }
+    }
+
+    pub(crate) fn state(&self) -> Option<JsValue> {
+        let state = match self {
+            // the hash history shares its states with the browser history
+            Self::Browser(AnyHistory::Hash(_)) => {
+                BrowserHistory::new().location().state::<JsValue>()
+            }
+            Self::Browser(history) => history.location().state::<JsValue>(),
+            Self::Memory(history) => history
+                .current(|entry| entry.state.clone())
+                .and_then(|state| state.downcast().ok()),
+        };
+        state.map(|state| (*state).clone())
+    }
+
+    /// Get the routes of all entries.
+    ///
+    /// The browser doesn't provide access to its entries, so only the current one is returned.
+    pub(crate) fn entries(&self) -> Vec<String> {
+        match self {
+            Self::Browser(_) => vec![self.route()],
+            Self::Memory(history) => history.entries(),
+        }
+    }
+
+    /// Move through the history, by the provided number of entries.
+    pub(crate) fn go(&self, delta: isize) {
+        match self {
+            Self::Browser(history) => history.go(delta),
+            Self::Memory(history) => history.go(delta),
+        }
+    }
+
+    /// Move to the entry at the provided index.
+    ///
+    /// The browser doesn't tell which entry is the current one, so this is only supported by the
+    /// in-memory history.
+    pub(crate) fn go_to(&self, index: usize) {
+        match self {
+            Self::Browser(_) => {
+                log::warn!("Navigating to a history entry is not supported by the browser history")
+            }
+            Self::Memory(history) => history.go(index as isize - history.index() as isize),
+        }
+    }
+
+    /// Get the number of entries before the current one.
+    ///
+    /// The browser only provides the total number of entries, so this assumes the current entry
+    /// to be the last one.
+    pub(crate) fn entries_back(&self) -> usize {
+        match self {
+            Self::Browser(_) => gloo_utils::window()
+                .history()
+                .and_then(|history| history.length())
+                .map(|length| (length as usize).saturating_sub(1))
+                .unwrap_or_default(),
+            Self::Memory(history) => history.index(),
+        }
+    }
+
+    /// Get the number of entries after the current one.
+    ///
+    /// Like for [`Self::entries_back`], for the browser the current entry is assumed to be the last
+    /// one, so there are none.
+    pub(crate) fn entries_forward(&self) -> usize {
+        match self {
+            Self::Browser(_) => 0,
+            Self::Memory(history) => history.len() - history.index() - 1,
+        }
+    }
+
+    pub(crate) fn listen<F>(&self, f: F) -> RouterListener
+    where
+        F: Fn() + 'static,
+    {
+        match self {
+            Self::Browser(history) => RouterListener::Browser {
+                _listener: history.listen(f),
+            },
+            Self::Memory(history) => RouterListener::Memory {
+                _listener: history.listen(f),
+            },
+        }
+    }
+}
+
+thread_local! {
+    /// In-memory histories, shared by their key.
+    static MEMORY_HISTORIES: RefCell<HashMap<String, MemoryHistory>> = RefCell::new(HashMap::new());
+}
+
+/// Get the current route of the hash history, from the fragment of the location.
+///
+/// The hash history fails on fragments which are not an absolute path, like `#section`, so all
+/// reads go through this instead.
+fn hash_location() -> MemoryEntry {
+    hash_entry(&gloo_utils::window().location().hash().unwrap_or_default())
+}
+
+/// Parse a fragment, like `#/users/42?page=1`, into the route it contains.
+///
+/// Fragments which don't start with a slash are considered absolute as well.
+fn hash_entry(hash: &str) -> MemoryEntry {
+    let mut entry = MemoryEntry::parse(hash.strip_prefix('#').unwrap_or(hash));
+    if !entry.path.starts_with('/') {
+        entry.path = format!("/{}", entry.path);
+    }
+    entry
+}
+
+/// Keep only the parameters with the provided names in the query string.
+pub(crate) fn filter_query(query: &str, keys: &[&str]) -> String {
+    let query = query
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default();
+            urlencoding::decode(key)
+                .map(|key| keys.contains(&key.as_ref()))
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+    match query.is_empty() {
+        true => query,
+        false => format!("?{query}"),
+    }
+}
+
+/// Split the query string into its (decoded) parameters.
+fn decode_query(query: &str) -> Vec<(String, String)> {
+    let decode = |s: &str| {
+        urlencoding::decode(s)
+            .map(|s| s.into_owned())
+            .unwrap_or_else(|_| s.to_string())
+    };
+    query
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (decode(key), decode(value)),
+            None => (decode(pair), String::new()),
+        })
+        .collect()
+}
+
+fn encode_query(query: HashMap<String, String>) -> String {
+    if query.is_empty() {
+        return String::new();
+    }
+
+    let query = query
+        .iter()
+        .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("?{query}")
+}
+
+#[derive(Clone)]
+struct MemoryEntry {
+    path: String,
+    query: String,
+    hash: String,
+    state: Option<Rc<dyn Any>>,
+}
+
+impl MemoryEntry {
+    fn parse(route: &str) -> Self {
+        let (route, hash) = match route.find('#') {
+            Some(pos) => route.split_at(pos),
+            None => (route, ""),
+        };
+        let (path, query) = match route.find('?') {
+            Some(pos) => route.split_at(pos),
+            None => (route, ""),
+        };
+
+        Self {
+            path: path.to_string(),
+            query: query.to_string(),
+            hash: hash.to_string(),
+            state: None,
+        }
+    }
+
+    fn route(&self) -> String {
+        format!("{}{}{}", self.path, self.query, self.hash)
+    }
+}
+
+struct MemoryStack {
+    entries: Vec<MemoryEntry>,
+    index: usize,
+    max_length: Option<usize>,
+    listeners: Vec<Weak<dyn Fn()>>,
+}
+
+impl MemoryStack {
+    /// Drop the oldest entries, exceeding the maximum length.
+    fn trim(&mut self) {
+        let max_length = match self.max_length {
+            // we always need to keep the current entry
+            Some(max_length) => max_length.max(1),
+            None => return,
+        };
+        if self.entries.len() > max_length {
+            let n = self.entries.len() - max_length;
+            self.entries.drain(..n);
+            self.index = self.index.saturating_sub(n);
+        }
+    }
+}
+
+/// An in-memory history.
+#[derive(Clone)]
+pub(crate) struct MemoryHistory {
+    inner: Rc<RefCell<MemoryStack>>,
+}
+
+impl PartialEq for MemoryHistory {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Debug for MemoryHistory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let inner = self.inner.borrow();
+        f.debug_struct("MemoryHistory")
+            .field("entries", &inner.entries.len())
+            .field("index", &inner.index)
+            .finish()
+    }
+}
+
+impl MemoryHistory {
+    /// Create a new history with the provided routes, the last one being the current entry.
+    ///
+    /// Without any routes, the history starts with the root route (`/`). If a maximum length is
+    /// provided, the oldest entries get dropped when exceeding it.
+    fn with_entries(routes: &[String], max_length: Option<usize>) -> Self {
+        let mut entries = routes
+            .iter()
+            .map(|route| MemoryEntry::parse(route))
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            entries.push(MemoryEntry::parse("/"));
+        }
+
+        let mut stack = MemoryStack {
+            index: entries.len() - 1,
+            entries,
+            max_length,
+            listeners: vec![],
+        };
+        stack.trim();
+
+        Self {
+            inner: Rc::new(RefCell::new(stack)),
+        }
+    }
+
+    fn current<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&MemoryEntry) -> R,
+    {
+        let inner = self.inner.borrow();
+        f(&inner.entries[inner.index])
+    }
+
+    fn index(&self) -> usize {
+        self.inner.borrow().index
+    }
+
+    fn len(&self) -> usize {
+        self.inner.borrow().entries.len()
+    }
+
+    fn entries(&self) -> Vec<String> {
+        self.inner
+            .borrow()
+            .entries
+            .iter()
+            .map(MemoryEntry::route)
+            .collect()
+    }
+
+    fn push(&self, entry: MemoryEntry) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            let index = inner.index;
+            // pushing drops all forward entries
+            inner.entries.truncate(index + 1);
+            inner.entries.push(entry);
+            inner.index += 1;
+            inner.trim();
+        }
+        self.notify();
+    }
+
+    /// Move the current entry, stopping at the first and last entry.
+    fn go(&self, delta: isize) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            let last = inner.entries.len() as isize - 1;
+            inner.index = (inner.index as isize + delta).clamp(0, last) as usize;
+        }
+        self.notify();
+    }
+
+    fn replace(&self, entry: MemoryEntry) {
+        self.update(|current| *current = entry);
+    }
+
+    fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut MemoryEntry),
+    {
+        {
+            let mut inner = self.inner.borrow_mut();
+            let index = inner.index;
+            f(&mut inner.entries[index]);
+        }
+        self.notify();
+    }
+
+    fn listen<F>(&self, f: F) -> Rc<dyn Fn()>
+    where
+        F: Fn() + 'static,
+    {
+        let listener = Rc::new(f) as Rc<dyn Fn()>;
+        self.inner
+            .borrow_mut()
+            .listeners
+            .push(Rc::downgrade(&listener));
+        listener
+    }
+
+    fn notify(&self) {
+        // collect first, so that listeners can access the history
+        let listeners = {
+            let mut inner = self.inner.borrow_mut();
+            inner
+                .listeners
+                .retain(|listener| listener.strong_count() > 0);
+            inner
+                .listeners
+                .iter()
+                .filter_map(Weak::upgrade)
+                .collect::<Vec<_>>()
+        };
+
+        for listener in listeners {
+            listener();
+        }
+    }
+}
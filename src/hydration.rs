@@ -0,0 +1,30 @@
+//! Passing the initial target of server-side rendered pages to the client.
+//!
+//! The server serializes the target it rendered into a `<script>` tag, from which the client
+//! deserializes it again before hydrating.
+
+use crate::target::Target;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Serialize the target to JSON, which is safe to embed into a `<script>` tag.
+pub fn serialize_initial_state<T>(target: &T) -> String
+where
+    T: Target + Serialize,
+{
+    match serde_json::to_string(target) {
+        // a string containing `</script>` would end the script tag
+        Ok(json) => json.replace('<', "\\u003c"),
+        Err(err) => {
+            log::warn!("Failed to serialize the initial state: {err}");
+            "null".to_string()
+        }
+    }
+}
+
+/// Deserialize a target, which was serialized using [`serialize_initial_state`].
+pub fn deserialize_initial_state<T>(json: &str) -> Option<T>
+where
+    T: Target + DeserializeOwned,
+{
+    serde_json::from_str(json).ok()
+}
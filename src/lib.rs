@@ -226,12 +226,20 @@
 //! See the `examples` folder.
 
 pub mod components;
+#[cfg(feature = "hydration")]
+pub mod hydration;
 pub mod target;
+pub mod transition;
 
 mod base;
+mod erased;
+mod guard;
+mod history;
 mod router;
 mod scope;
+mod static_router;
 mod switch;
+mod testing;
 
 pub use router::Router;
 pub use scope::Scope;
@@ -240,10 +248,15 @@ pub use yew_nested_router_macros::Target;
 
 /// Common includes.
 pub mod prelude {
+    pub use super::erased::*;
+    pub use super::guard::*;
     pub use super::router::*;
     pub use super::scope::*;
+    pub use super::static_router::*;
     pub use super::switch::*;
     pub use super::target::*;
+    pub use super::testing::*;
+    pub use super::transition::*;
 
     pub use yew_nested_router_macros::Target;
 }
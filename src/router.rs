@@ -1,10 +1,18 @@
 use crate::base;
-use crate::scope::ScopeContext;
-use crate::target::Target;
-use gloo_history::{AnyHistory, BrowserHistory, History, HistoryListener, Location};
-use std::borrow::Cow;
+use crate::erased::ErasedRouterContext;
+use crate::guard::RouterGuardContext;
+use crate::history::{filter_query, RouterHistory, RouterListener};
+use crate::scope::{DelayedNavigations, Navigation, ScopeContext, VisitedTargets};
+use crate::target::{split_path, ParseContext, Target};
+use crate::transition::{RouteAnimation, TransitionHandle};
+use gloo_timers::callback::Timeout;
+use serde::{de::DeserializeOwned, Serialize};
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
 use yew::prelude::*;
 
 #[derive(Clone, PartialEq)]
@@ -15,6 +23,10 @@ where
     pub(crate) scope: Rc<ScopeContext<T>>,
     // The active target
     pub active_target: Option<T>,
+    // The target of a pending transition
+    pub(crate) pending_target: Option<T>,
+    // The animation of the transition to the active target
+    pub(crate) animation: Option<RouteAnimation>,
 }
 
 impl<T> RouterContext<T>
@@ -26,6 +38,472 @@ where
         self.scope.push(target);
     }
 
+    /// Pass a navigation request to the parent scope, which translates it to its own target type
+    /// and passes it on, up to the top-level router.
+    ///
+    /// This is what all navigation requests of a nested context do, and so is the same as
+    /// [`Self::push`]. It is intended for custom components, working with the scopes directly.
+    pub fn emit_to_parent(&self, target: T) {
+        self.scope.push(target);
+    }
+
+    /// Replace the current state of the history. This changes the current target, without adding
+    /// a new history entry.
+    pub fn replace(&self, target: T) {
+        self.scope.replace(target);
+    }
+
+    /// Push a new state to the history, converting the target into the target type first.
+    ///
+    /// This allows to navigate using a different type, like the target of a nested module.
+    pub fn navigate_typed<U: Into<T>>(&self, target: U) {
+        self.push(target.into());
+    }
+
+    /// Replace the current state of the history, converting the target into the target type
+    /// first.
+    pub fn replace_typed<U: Into<T>>(&self, target: U) {
+        self.replace(target.into());
+    }
+
+    /// Push a new state to the history, converting the target into the target type first, unless
+    /// it is already the active target.
+    pub fn go_if_different_typed<U: Into<T>>(&self, target: U) {
+        self.go_if_different(target.into());
+    }
+
+    /// Push a new state to the history, unless the target is already the active target.
+    ///
+    /// This prevents duplicate history entries, in case the same navigation is triggered multiple
+    /// times.
+    pub fn go_if_different(&self, target: T) {
+        self.go_if_different_or(target, SameTarget::Ignore);
+    }
+
+    /// Push a new state to the history, unless the target is already the active target. In that
+    /// case, the provided behavior is applied.
+    pub fn go_if_different_or(&self, target: T, same: SameTarget) {
+        match (self.is_same(&target), same) {
+            (false, _) => self.push(target),
+            (true, SameTarget::Ignore) => {}
+            (true, SameTarget::Replace) => self.replace(target),
+        }
+    }
+
+    /// Push the first of the provided targets to the history.
+    ///
+    /// Returns `false` if there was no target to navigate to.
+    pub fn go_to_first_matching(&self, targets: impl IntoIterator<Item = T>) -> bool {
+        self.go_to_first_where(targets, |_| true)
+    }
+
+    /// Push the first of the provided targets, matching the predicate, to the history.
+    ///
+    /// This allows to navigate to the first permitted target, of an ordered list of preferences.
+    /// Returns `false` if no target matched.
+    pub fn go_to_first_where<F>(&self, targets: impl IntoIterator<Item = T>, predicate: F) -> bool
+    where
+        F: Fn(&T) -> bool,
+    {
+        match targets.into_iter().find(|target| predicate(target)) {
+            Some(target) => {
+                self.push(target);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Push the first of the candidates which is allowed by the guards of the router, see
+    /// [`RouterGuardContext`]. For example, once the permissions of the user are known.
+    ///
+    /// Returns `true` if a candidate was allowed. Otherwise, this pushes the default target of the
+    /// router, like [`Self::go_to_default`], and returns `false`.
+    pub fn go_to_matching_or_default(&self, candidates: &[T]) -> bool {
+        let allowed = self.go_to_first_where(candidates.iter().cloned(), |target| {
+            self.scope.allowed.emit(target.clone())
+        });
+        if !allowed {
+            self.go_to_default();
+        }
+        allowed
+    }
+
+    /// Push the tab with the provided index to the history.
+    ///
+    /// Returns `false` if the index is out of bounds.
+    pub fn go_to_tab(&self, index: usize, tabs: &[T]) -> bool {
+        match tabs.get(index) {
+            Some(tab) => {
+                self.push(tab.clone());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the index of the tab, which is the active target.
+    pub fn active_tab_index(&self, tabs: &[T]) -> Option<usize> {
+        tabs.iter().position(|tab| self.is_same(tab))
+    }
+
+    /// Push a new state to the history, using an animation for the transition.
+    ///
+    /// The animation will be available using [`Self::animation`], once the target is active.
+    pub fn go_with_animation(&self, target: T, animation: RouteAnimation) {
+        self.scope.push_animated(target, animation);
+    }
+
+    /// Start a transition to a new target, which gets pushed to the history once the returned
+    /// handle is committed or dropped.
+    ///
+    /// Until then, [`Self::active`] still returns the previous target, while [`Self::pending`]
+    /// returns the new target. This allows to keep the previous content around, e.g. for an
+    /// exit animation.
+    pub fn go_with_transition(&self, target: T) -> TransitionHandle<T> {
+        TransitionHandle::new(self.scope.clone(), target)
+    }
+
+    /// Get the target of a pending transition, started by [`Self::go_with_transition`].
+    pub fn pending(&self) -> Option<&T> {
+        self.pending_target.as_ref()
+    }
+
+    /// Check if a pending transition goes to the target, e.g. for an enter animation.
+    pub fn is_transitioning_to(&self, target: &T) -> bool {
+        self.pending_target.as_ref() == Some(target)
+    }
+
+    /// Check if a pending transition comes from the target, which is still the active one, e.g.
+    /// for an exit animation.
+    pub fn is_transitioning_from(&self, target: &T) -> bool {
+        self.pending_target.is_some() && self.active_target.as_ref() == Some(target)
+    }
+
+    /// Push a new state to the history, setting the title of the document once the target is
+    /// active.
+    ///
+    /// The title overrides the title of the target's metadata for this navigation, and is set
+    /// even if the `update_title` property of the router is not enabled.
+    pub fn go_with_title(&self, target: T, title: &str) {
+        self.scope.push_titled(target, title.to_string());
+    }
+
+    /// Dispatch a `yew:navigate` custom event on the document, with the URL of the target as
+    /// `detail.path`, for observing the navigation from JavaScript, e.g. in web components.
+    ///
+    /// The top-level router dispatches the event after each change of the active target. Without
+    /// a document, e.g. when rendering on the server, this does nothing.
+    pub fn emit_navigate_event(&self, target: &T) {
+        let document = match web_sys::window().and_then(|window| window.document()) {
+            Some(document) => document,
+            None => return,
+        };
+
+        let detail = js_sys::Object::new();
+        let path = JsValue::from(self.format_path(target));
+        if let Err(err) = js_sys::Reflect::set(&detail, &JsValue::from("path"), &path) {
+            log::warn!("Failed to create the navigate event: {err:?}");
+            return;
+        }
+
+        let init = web_sys::CustomEventInit::new();
+        init.set_detail(&detail);
+        let result = web_sys::CustomEvent::new_with_event_init_dict("yew:navigate", &init)
+            .and_then(|event| document.dispatch_event(&event));
+        if let Err(err) = result {
+            log::warn!("Failed to dispatch the navigate event: {err:?}");
+        }
+    }
+
+    /// Push a new target to the history after a delay, e.g. leaving a success page after a few
+    /// seconds. If `replace` is `true`, it replaces the current history entry instead.
+    ///
+    /// Multiple delayed navigations may be pending at the same time. They are cancelled when the
+    /// router is removed.
+    pub fn go_after_delay(&self, target: T, delay_ms: u32, replace: bool) {
+        self.scope.push_delayed(target, delay_ms, replace);
+    }
+
+    /// Get the attributes of a link to the target, without rendering it, for headless link
+    /// components which render their own element.
+    pub fn get_link_props(&self, target: &T) -> LinkAttributes {
+        let is_exact = self.is_same(target);
+        let is_active = match &self.active_target {
+            Some(active) => is_same_or_below(&segments_of(active), &segments_of(target)),
+            None => false,
+        };
+
+        let onclick = {
+            let router = self.clone();
+            let target = target.clone();
+            Callback::from(move |e: MouseEvent| {
+                // let the browser handle opening the link in a new tab or window
+                if e.ctrl_key() || e.meta_key() || e.shift_key() || e.alt_key() || e.button() != 0 {
+                    return;
+                }
+                // only anchors navigate by themselves, other elements (like the button of a form)
+                // keep their default action
+                let anchor = e
+                    .current_target()
+                    .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                    .map(|element| element.tag_name().eq_ignore_ascii_case("a"))
+                    .unwrap_or_default();
+                if anchor {
+                    e.prevent_default();
+                }
+                router.push(target.clone());
+            })
+        };
+
+        LinkAttributes {
+            href: self.format_path(target),
+            onclick,
+            is_active,
+            is_exact,
+        }
+    }
+
+    /// Open an external URL in a new tab or window, leaving the history of the router untouched.
+    pub fn go_external_blank(&self, url: &str) {
+        if let Err(err) = gloo_utils::window().open_with_url_and_target(url, "_blank") {
+            log::warn!("Failed to open {url}: {err:?}");
+        }
+    }
+
+    /// Get the animation of the transition to the active target, if there was one requested.
+    pub fn animation(&self) -> Option<&RouteAnimation> {
+        self.animation.as_ref()
+    }
+
+    /// Push the default target of the router to the history.
+    ///
+    /// This uses the `default` property of the router, see [`Self::go_home`] and
+    /// [`Self::go_to_root`] for navigating to the root path instead. If the router has no default
+    /// target configured, this does nothing.
+    pub fn go_to_default(&self) {
+        self.scope.go_default();
+    }
+
+    /// Push the error target of the router to the history, created by its `error_route` property
+    /// from the status code and message.
+    ///
+    /// If the router has no error route configured, a warning is logged instead.
+    pub fn navigate_to_error(&self, code: u16, message: &str) {
+        self.scope.go_error(code, message.to_string());
+    }
+
+    /// Push the target of the root path (`/`, within the application base) to the history.
+    ///
+    /// In contrast to [`Self::go_to_default`], this uses the target matching the root path. Only
+    /// if the root path doesn't match any target, the default target of the router is used.
+    pub fn go_home(&self) {
+        self.scope.go_home();
+    }
+
+    /// Push the root path (`/`, within the application base) to the history, as it is.
+    ///
+    /// In contrast to [`Self::go_home`], no target is resolved before navigating, and there is no
+    /// fallback to the default target. The path is parsed like any other location, so the root
+    /// path decides what is rendered, and the router handles it not matching a target. As there
+    /// is no target, the guards of the router are not checked.
+    pub fn go_to_root(&self) {
+        self.scope.go_root();
+    }
+
+    /// Go up one level, by removing the last segment of the current path.
+    ///
+    /// The resulting path is handled by the top-level router. If it doesn't match a target,
+    /// this does nothing.
+    pub fn go_pop(&self) {
+        self.scope.go_up();
+    }
+
+    /// Go to a sibling of the active target, like switching between tabs.
+    ///
+    /// Within a nested context, the path of the parent is kept by the scopes, so this pushes the
+    /// sibling, like [`Self::push`]. However, it checks that the sibling shares all but the last
+    /// path segment with the active target, and logs a warning if it doesn't.
+    pub fn go_to_sibling(&self, sibling: T) {
+        if let Some(active) = &self.active_target {
+            let active = active.render_path();
+            let path = sibling.render_path();
+            let parent = |path: &[String]| path[..path.len().saturating_sub(1)].to_vec();
+            if parent(&active) != parent(&path) {
+                log::warn!("Target {sibling:?} is not a sibling of the active target");
+            }
+        }
+        self.push(sibling);
+    }
+
+    /// Go to the most recently visited child of the root target, like returning to the last page
+    /// of a section in breadcrumb-style navigation.
+    ///
+    /// Returns `true` if a child of the root target was visited before, and got pushed. Otherwise,
+    /// this pushes the root target itself, and returns `false`.
+    pub fn go_to_deepest_child(&self, root: &T) -> bool {
+        match self.scope.visited.last_child(root) {
+            Some(child) if &child != root => {
+                self.push(child);
+                true
+            }
+            _ => {
+                self.push(root.clone());
+                false
+            }
+        }
+    }
+
+    /// Go to the parent of the active target, by removing the last segment of its path.
+    ///
+    /// In contrast to [`Self::go_pop`], this works on the (local) target of this context. If the
+    /// resulting path doesn't match a target of this level, a warning is logged instead.
+    pub fn go_to_parent(&self) {
+        let mut path = match &self.active_target {
+            Some(target) => target.render_path(),
+            None => {
+                log::warn!("No active target to go to the parent of");
+                return;
+            }
+        };
+        path.pop();
+        let path = parse_segments(&path);
+        match T::parse_path(&path) {
+            Some(target) => self.push(target),
+            None => log::warn!("No target matches the parent path: {path:?}"),
+        }
+    }
+
+    /// Go to the child of the active target, by appending a segment to its path, like opening a
+    /// folder in a tree view.
+    ///
+    /// Returns `false`, and does nothing, if there is no active target or the resulting path
+    /// doesn't match a target.
+    pub fn go_to_segment(&self, segment: &str) -> bool {
+        let mut path = match &self.active_target {
+            Some(target) => segments_of(target),
+            None => return false,
+        };
+        path.push(segment.to_string());
+        let path = path.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        match T::parse_path(&path) {
+            Some(target) => {
+                self.push(target);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Go to a path relative to the path of the active target, like `../sibling`.
+    ///
+    /// The path is resolved like the relative `href` of a link in the browser: relative to the
+    /// parent of the active target, normalizing `.` and `..` segments. A path starting with `/`
+    /// is resolved from the root of this level. Returns `false`, and logs a warning, if there is
+    /// no active target or the resolved path doesn't match a target.
+    pub fn go_to_relative_path(&self, delta: &str) -> bool {
+        let mut path = match &self.active_target {
+            Some(target) => target.render_path(),
+            None => {
+                log::warn!("No active target to resolve the relative path {delta} against");
+                return false;
+            }
+        };
+        path.pop();
+        if delta.starts_with('/') {
+            path.clear();
+        }
+
+        for segment in delta.split('/') {
+            match segment {
+                "" | "." => {}
+                ".." => {
+                    path.pop();
+                }
+                segment => path.push(
+                    urlencoding::decode(segment)
+                        .map(|segment| segment.into_owned())
+                        .unwrap_or_else(|_| segment.to_string()),
+                ),
+            }
+        }
+
+        let path = parse_segments(&path);
+        match T::parse_path(&path) {
+            Some(target) => {
+                self.push(target);
+                true
+            }
+            None => {
+                log::warn!("No target matches the relative path {delta}: {path:?}");
+                false
+            }
+        }
+    }
+
+    /// Push a new target to the history, keeping the query string of the current entry, like
+    /// tracking parameters or debug flags.
+    ///
+    /// If `preserve_keys` is provided, only the query parameters with those names are kept.
+    pub fn go_preserving_query(&self, target: T, preserve_keys: Option<&[&str]>) {
+        let query = self.scope.history.query();
+        let query = match preserve_keys {
+            Some(keys) => filter_query(&query, keys),
+            None => query,
+        };
+        self.scope.push_with_query(target, query);
+    }
+
+    /// Replace the query string of the current history entry.
+    ///
+    /// The path, and so the active target, stays the same.
+    pub fn go_replacing_query(&self, query: HashMap<String, String>) {
+        self.scope.replace_query(query);
+    }
+
+    /// Replace the active target with a modified copy, like changing the sort order of a list,
+    /// without adding a new history entry.
+    ///
+    /// Returns `false`, without navigating, if there is no active target.
+    pub fn replace_current_target<F>(&self, f: F) -> bool
+    where
+        F: FnOnce(T) -> T,
+    {
+        match self.active_target.clone() {
+            Some(target) => {
+                self.replace(f(target));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replace named values of the active target, like `id` of `User { id: String }`, without
+    /// adding a new history entry.
+    ///
+    /// Returns `false`, without navigating, if there is no active target, a name is not a named
+    /// value of the active target, or a value can't be parsed.
+    pub fn replace_params(&self, params: HashMap<String, String>) -> bool {
+        let target = match &self.active_target {
+            Some(target) => target,
+            None => return false,
+        };
+
+        let captures = target.captures();
+        if let Some(name) = params.keys().find(|name| !captures.contains_key(*name)) {
+            log::debug!("Unknown parameter: {name}");
+            return false;
+        }
+
+        match target.with_captures(&params) {
+            Some(target) => {
+                self.replace(target);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Check if the provided target is the active target
     pub fn is_same(&self, target: &T) -> bool {
         match &self.active_target {
@@ -34,6 +512,11 @@ where
         }
     }
 
+    /// Check if the active target differs from a previous one, e.g. in effects.
+    pub fn active_changed_from(&self, old: &Option<T>) -> bool {
+        &self.active_target != old
+    }
+
     /// Check if the target is active.
     ///
     /// This is intended for components to find out if their target, or part of their target
@@ -59,13 +542,363 @@ where
         }
     }
 
+    /// Attach a state to the current history entry, without navigating.
+    ///
+    /// The state is serialized, and will be available again using [`Self::state`] when
+    /// navigating back to this entry.
+    pub fn set_state<S: Serialize>(&self, state: S) {
+        match serde_wasm_bindgen::to_value(&state) {
+            Ok(state) => self.scope.replace_state(state),
+            Err(err) => log::warn!("Failed to serialize state: {err}"),
+        }
+    }
+
+    /// Get the state of the current history entry, if it was set using [`Self::set_state`] and
+    /// can be deserialized into `S`.
+    pub fn state<S: DeserializeOwned>(&self) -> Option<S> {
+        self.scope
+            .state()
+            .and_then(|state| serde_wasm_bindgen::from_value(state).ok())
+    }
+
+    /// Get the routes of all history entries, e.g. for persisting them in the session storage.
+    ///
+    /// The history can be restored using [`RouterMode::restore_from_history`]. This is only fully
+    /// supported with the [`RouterMode::Memory`] mode. The browser doesn't provide access to its
+    /// history entries, so only the current entry is returned in that case.
+    pub fn serialize_history(&self) -> Vec<String> {
+        self.scope.history.entries()
+    }
+
+    /// Generate the URLs of all targets, which can be enumerated using [`Target::children`].
+    ///
+    /// The URLs are prefixed with the provided base URL (like `https://example.com`), followed by
+    /// the path as returned by [`Self::format_path`].
+    pub fn generate_sitemap(&self, base_url: &str) -> Vec<String> {
+        let base_url = base_url.trim_end_matches('/');
+        T::children()
+            .iter()
+            .map(|target| format!("{base_url}{}", self.format_path(target)))
+            .collect()
+    }
+
+    /// Get the URL of the active target, including the query string, e.g. for canonical links or
+    /// analytics.
+    ///
+    /// The path is formatted using [`Self::format_path`]. Without an active target, the path
+    /// of the current history entry is used, including the application base.
+    pub fn effective_path(&self) -> String {
+        let path = match &self.active_target {
+            Some(target) => self.format_path(target),
+            None => self.scope.history.path(),
+        };
+        format!("{path}{}", self.scope.history.query())
+    }
+
+    /// Get the URL of the active target as string, including the application base and the query
+    /// string.
+    ///
+    /// This is the same as [`Self::effective_path`], and the preferred way over joining the
+    /// segments of [`Target::render_path`], which misses the base, encoding, and the query string.
+    pub fn current_path_string(&self) -> String {
+        self.effective_path()
+    }
+
+    /// Get the local path of a target as string, like `/users/42`, without the application base
+    /// or the path of parent targets.
+    ///
+    /// The segments are percent-encoded, and the root target is rendered as `/`. The result can
+    /// be parsed again using [`Target::parse_str`].
+    pub fn path_string_for(target: &T) -> String {
+        render_route("", target)
+    }
+
+    /// Format the URL of a target, e.g. for the `href` attribute of a link.
+    ///
+    /// The path is percent-encoded, and includes the application base of the router as well as
+    /// the path of all parent targets. For the [`RouterMode::Hash`] mode, the path is returned
+    /// as fragment (`#/path`).
+    pub fn format_path(&self, target: &T) -> String {
+        self.scope.route.emit(target.clone())
+    }
+
+    /// Create a copy of the context, which prefixes the URLs it formats with a basename, like
+    /// `/embedded`, e.g. for a sub-application embedded into a host application.
+    ///
+    /// This applies to [`Self::format_path`] and [`Self::get_link_props`]. Navigating still
+    /// goes through the router, which uses its own application base. To change that at runtime,
+    /// change the `base` property of the [`Router`].
+    pub fn with_basename(&self, basename: &str) -> Self {
+        let basename = basename.trim_end_matches('/').to_string();
+        let route = self.scope.route.clone();
+        let scope = ScopeContext {
+            route: Callback::from(move |target: T| format!("{basename}{}", route.emit(target))),
+            ..(*self.scope).clone()
+        };
+        Self {
+            scope: Rc::new(scope),
+            ..self.clone()
+        }
+    }
+
+    /// Go back one history entry, if there is one. Otherwise, replace the current entry with the
+    /// default target of the router, if there is one.
+    ///
+    /// Returns `true` if it went back. For the browser history, the check uses an approximation,
+    /// see [`Self::entries_back`].
+    pub fn go_back_if_possible(&self) -> bool {
+        if self.entries_back() > 0 {
+            self.scope.history.go(-1);
+            return true;
+        }
+        if let Some(default) = &self.scope.default {
+            self.replace(default.clone());
+        }
+        false
+    }
+
+    /// Go to the history entry with the provided index, the first entry having the index `0`.
+    ///
+    /// This is only supported with the [`RouterMode::Memory`] mode, as the browser doesn't tell
+    /// which entry is the current one. Otherwise, a warning is logged instead.
+    pub fn go_history_entry(&self, index: usize) {
+        self.scope.history.go_to(index);
+    }
+
+    /// Get the number of history entries before the current one, e.g. for showing a "back" button.
+    ///
+    /// This is exact for the [`RouterMode::Memory`] mode. For the browser history, this is an
+    /// approximation, as the browser doesn't tell which entry is the current one.
+    pub fn entries_back(&self) -> usize {
+        self.scope.history.entries_back()
+    }
+
+    /// Get the number of history entries after the current one, e.g. for showing a "forward"
+    /// button.
+    ///
+    /// This is exact for the [`RouterMode::Memory`] mode. The browser doesn't tell which entry
+    /// is the current one, so for the browser history, this is always `0`.
+    pub fn entries_forward(&self) -> usize {
+        self.scope.history.entries_forward()
+    }
+
+    /// Take a snapshot of the current state, e.g. for debugging.
+    pub fn snapshot(&self) -> RouterSnapshot<T> {
+        RouterSnapshot {
+            active_target: self.active_target.clone(),
+            history_index: self.entries_back(),
+            timestamp: now(),
+        }
+    }
+
+    /// Jump back (or forward) to the history entry of a snapshot.
+    ///
+    /// If the snapshot was taken on the current history entry, but with a different target, the
+    /// target of the snapshot replaces the current one. For the browser history, the history
+    /// index is only an approximation, see [`Self::entries_back`].
+    pub fn restore_snapshot(&self, snapshot: RouterSnapshot<T>) {
+        let delta = snapshot.history_index as isize - self.entries_back() as isize;
+        if delta != 0 {
+            self.scope.history.go(delta);
+            return;
+        }
+        if let Some(target) = snapshot.active_target {
+            if !self.is_same(&target) {
+                self.replace(target);
+            }
+        }
+    }
+
     /// Get the active target, this may be [`None`], in the case this branch doesn't have an
     /// active target.
     pub fn active(&self) -> &Option<T> {
         &self.active_target
     }
+
+    /// Get the active target, converted into a different type, like the target of a nested enum.
+    ///
+    /// Returns [`None`] if there is no active target, or it can't be converted.
+    pub fn active_matches<R: TryFrom<T>>(&self) -> Option<R> {
+        self.active_target
+            .clone()
+            .and_then(|target| R::try_from(target).ok())
+    }
+
+    /// Call the function with the active target, if there is one.
+    pub fn with_target<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.active_target.as_ref().map(f)
+    }
+
+    /// Call the function with the active target, or return the default if there is none.
+    pub fn with_target_or<R>(&self, default: R, f: impl FnOnce(&T) -> R) -> R {
+        self.active_target.as_ref().map(f).unwrap_or(default)
+    }
+
+    /// Get the active target, falling back to the default target of the router.
+    ///
+    /// In a nested context, the default target of the router is mapped to the target type of
+    /// this level.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no active target, and no default target is configured (or it doesn't
+    /// map to this level).
+    pub fn active_target_or_default(&self) -> &T {
+        self.active_target
+            .as_ref()
+            .or(self.scope.default.as_ref())
+            .expect("No active target, and no default target configured for the router")
+    }
+
+    /// Get the number of path segments of the active target, or `0` if there is none.
+    ///
+    /// Empty segments, like the ones of index targets, are not counted. So the index target of
+    /// the root has a depth of `0`.
+    pub fn active_depth(&self) -> usize {
+        self.active_target
+            .as_ref()
+            .map(|target| {
+                target
+                    .render_path()
+                    .iter()
+                    .filter(|segment| !segment.is_empty())
+                    .count()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Get the named values of the active target, like `id` of `User { id: String }`, as strings.
+    ///
+    /// This includes the values of all nested targets, see [`Target::captures`]. Without an
+    /// active target, this is empty.
+    pub fn path_parameters(&self) -> HashMap<String, String> {
+        self.active_target
+            .as_ref()
+            .map(|target| target.captures())
+            .unwrap_or_default()
+    }
+
+    /// Get the ancestors of the active target, like for rendering breadcrumbs.
+    ///
+    /// The targets are ordered from the root to the immediate parent, for all prefixes of the path
+    /// of the active target which match a target. The active target itself is not included.
+    /// Without an active target, this is empty.
+    pub fn ancestors(&self) -> Vec<T> {
+        let path = match &self.active_target {
+            Some(target) => segments_of(target),
+            None => return vec![],
+        };
+        (0..path.len())
+            .filter_map(|n| T::parse_path(&parse_segments(&path[..n])))
+            .collect()
+    }
+
+    /// Get the breadcrumbs of the active target, from the root to the active target itself, with
+    /// their labels.
+    ///
+    /// The targets are the [`Self::ancestors`] and the active target, the labels are their
+    /// [`Target::display_name`]. The URL of a target can be created using [`Self::format_path`].
+    /// Without an active target, this is empty.
+    pub fn breadcrumb_targets(&self) -> Vec<(T, String)> {
+        self.ancestors()
+            .into_iter()
+            .chain(self.active_target.clone())
+            .map(|target| {
+                let label = target.display_name();
+                (target, label)
+            })
+            .collect()
+    }
+
+    /// Check if the path segment of the active target at the index matches the value, without
+    /// knowing the target type.
+    ///
+    /// Unless `exact` is `true`, the comparison ignores the (ASCII) case. Without an active
+    /// target, or a segment at the index, this returns `false`.
+    pub fn matches_segment_at(&self, index: usize, value: &str, exact: bool) -> bool {
+        self.active_target
+            .as_ref()
+            .and_then(|target| target.render_path().into_iter().nth(index))
+            .map(|segment| match exact {
+                true => segment == value,
+                false => segment.eq_ignore_ascii_case(value),
+            })
+            .unwrap_or_default()
+    }
+
+    /// Replace the path segment of the active target at the index with the value, keeping all
+    /// other segments, like switching the language of `/en/page` to `/fr/page`.
+    ///
+    /// If the resulting path matches a target, it replaces the current history entry. Returns
+    /// `None` if there is no active target, no segment at the index, or the resulting path doesn't
+    /// match a target.
+    pub fn replace_segment_at(&self, index: usize, value: &str) -> Option<()> {
+        let mut path = self.active_target.as_ref()?.render_path();
+        *path.get_mut(index)? = value.to_string();
+        let path = path.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        self.replace(T::parse_path(&path)?);
+        Some(())
+    }
+
+    /// Check if the active target is the root, having an empty path or being the index target.
+    ///
+    /// Without an active target, this returns `false`, see [`Self::is_unmatched`].
+    pub fn is_root(&self) -> bool {
+        self.active_target
+            .as_ref()
+            .map(|target| target.is_index() || target.render_path().is_empty())
+            .unwrap_or_default()
+    }
+
+    /// Check if there is no active target.
+    pub fn is_unmatched(&self) -> bool {
+        self.active_target.is_none()
+    }
+
+    /// Create a copy of this context, with a different active target, e.g. for testing.
+    ///
+    /// Navigation requests of the copy are still handled by the original router.
+    pub fn clone_with_target(&self, target: T) -> Self {
+        Self {
+            active_target: Some(target),
+            ..self.clone()
+        }
+    }
+
+    /// Get a type-erased version of this context, working on path segments.
+    pub fn as_erased(&self) -> ErasedRouterContext {
+        let scope = self.scope.clone();
+        ErasedRouterContext {
+            active_path: self
+                .active_target
+                .as_ref()
+                .map(|target| target.render_path()),
+            navigate: Callback::from(move |path: Vec<String>| {
+                let path = path.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+                match T::parse_path(&path) {
+                    Some(target) => scope.push(target),
+                    None => log::warn!("No target matches the path: {path:?}"),
+                }
+            }),
+        }
+    }
+}
+
+/// The behavior when navigating to a target which is already active.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SameTarget {
+    /// Don't navigate.
+    Ignore,
+    /// Replace the current history entry.
+    Replace,
 }
 
+/// The default of [`RouterProps::anchor_scroll`].
+const DEFAULT_ANCHOR_SCROLL: bool = true;
+
+/// The default of [`RouterProps::persist_to_session_storage`].
+const DEFAULT_PERSIST_TO_SESSION_STORAGE: bool = cfg!(debug_assertions);
+
 /// Properties for the [`Router`] component.
 #[derive(Clone, Debug, PartialEq, Properties)]
 pub struct RouterProps<T>
@@ -85,7 +918,8 @@ where
     /// Defaults to an empty string or the content of the `href` attribute of the `<base>` element.
     ///
     /// This can be used in case the application is hosted on a sub path to adapt paths generated
-    /// and expected by the router.
+    /// and expected by the router. It may change at runtime, e.g. when the application is
+    /// embedded into another one, in which case the target is evaluated again.
     ///
     /// ## Usage with `trunk`
     ///
@@ -102,25 +936,402 @@ where
     /// `--public-url` argument.
     #[prop_or_default]
     pub base: Option<String>,
+
+    /// Called when the current location could not be parsed into a target, and no default
+    /// target is configured.
+    #[prop_or_default]
+    pub on_error: Option<Callback<RouteError>>,
+
+    /// Called with the raw path, when the current location could not be parsed into a target,
+    /// and no default target is configured.
+    ///
+    /// This is called in addition to `on_error`, e.g. for logging missing pages or redirecting
+    /// to a custom error page.
+    #[prop_or_default]
+    pub on_not_found: Option<Callback<String>>,
+
+    /// Scroll to the element referenced by the fragment (`#id`) of the location, after navigating.
+    ///
+    /// Navigating using the history API bypasses the browser's native behavior of scrolling to
+    /// the fragment. This restores it.
+    #[prop_or(DEFAULT_ANCHOR_SCROLL)]
+    pub anchor_scroll: bool,
+
+    /// Context, handed over to [`Target::parse_path_with_context`] when parsing the location.
+    #[prop_or_default]
+    pub parse_context: ParseContext,
+
+    /// Set the document title from the [`crate::target::RouteMetadata::title`] of the active
+    /// target, if it has one.
+    #[prop_or_default]
+    pub update_title: bool,
+
+    /// The mode of the router.
+    ///
+    /// This is only evaluated when the router is created.
+    #[prop_or_default]
+    pub mode: RouterMode,
+
+    /// The maximum number of history entries to keep, dropping the oldest ones.
+    ///
+    /// This only applies to the [`RouterMode::Memory`] mode, and is ignored otherwise, as the
+    /// browser controls the length of its history. It is only evaluated when the router is
+    /// created.
+    #[prop_or_default]
+    pub max_history_length: Option<usize>,
+
+    /// A key, sharing the history with all routers using the same key.
+    ///
+    /// This only applies to the [`RouterMode::Memory`] mode, and is ignored otherwise. Routers
+    /// with different keys, or without a key, have independent histories. A history with a key
+    /// is kept when the router gets removed, and reused when a router with the same key gets
+    /// created again. In this case, the initial entries and the maximum length of the new
+    /// router are ignored. It is only evaluated when the router is created.
+    #[prop_or_default]
+    pub history_key: Option<AttrValue>,
+
+    /// How to handle paths with a trailing slash, like `/settings/`.
+    ///
+    /// Defaults to [`TrailingSlashPolicy::Allow`].
+    ///
+    /// **This default changes the existing behavior:** previous versions parsed the path as it
+    /// is, so `/settings/` didn't match a `/settings` target. Set this to
+    /// [`TrailingSlashPolicy::Reject`] to keep that behavior.
+    #[prop_or_default]
+    pub strict_trailing_slash: Option<TrailingSlashPolicy>,
+
+    /// A key, persisting the path of the active target to the `sessionStorage`.
+    ///
+    /// When the router gets created again, e.g. because the component tree was remounted, the
+    /// target is restored from the storage instead of parsing the location, which then gets
+    /// replaced with the path of the restored target. It is only evaluated when the router is
+    /// created.
+    #[prop_or_default]
+    pub persist_key: Option<AttrValue>,
+
+    /// Keep the target of an in-memory history across reloads during development, like the live
+    /// reload of `trunk`.
+    ///
+    /// This only applies to the [`RouterMode::Memory`] mode, as the browser keeps the location
+    /// itself, and not to routers with a `history_key`, like the ones of a
+    /// [`crate::prelude::RouterTestHandle`]. It works like the `persist_key`, using the key
+    /// `__yew_router_dev_path__:` followed by the type name of the target. In contrast to a plain
+    /// `__yew_router_dev_path__` key, routers of different types don't overwrite each other's
+    /// target. However, the target is only restored once after loading the page, and not when
+    /// the router gets created again. It defaults to `true` in debug builds, and is always
+    /// ignored in release builds, and outside the browser, like for server-side rendering.
+    #[prop_or(DEFAULT_PERSIST_TO_SESSION_STORAGE)]
+    pub persist_to_session_storage: bool,
+
+    /// Create the target of an error page, from a status code and a message, used by
+    /// [`RouterContext::navigate_to_error`].
+    #[prop_or_default]
+    pub error_route: Option<Callback<(u16, String), T>>,
+}
+
+/// Uses the same defaults as the `html!` macro does for missing properties.
+impl<T> Default for RouterProps<T>
+where
+    T: Target,
+{
+    fn default() -> Self {
+        Self {
+            children: Default::default(),
+            default: Default::default(),
+            base: Default::default(),
+            on_error: Default::default(),
+            on_not_found: Default::default(),
+            anchor_scroll: DEFAULT_ANCHOR_SCROLL,
+            parse_context: Default::default(),
+            update_title: Default::default(),
+            mode: Default::default(),
+            max_history_length: Default::default(),
+            history_key: Default::default(),
+            strict_trailing_slash: Default::default(),
+            persist_key: Default::default(),
+            persist_to_session_storage: DEFAULT_PERSIST_TO_SESSION_STORAGE,
+            error_route: Default::default(),
+        }
+    }
+}
+
+/// The handling of paths with a trailing slash, like `/settings/`.
+///
+/// This doesn't apply to the root path (`/`). The default, [`Self::Allow`], changes the behavior
+/// of previous versions, which matched [`Self::Reject`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrailingSlashPolicy {
+    /// Treat the path the same as without the trailing slash.
+    Allow,
+    /// Treat the path the same as without the trailing slash, and replace the URL with the one of
+    /// the target.
+    Redirect,
+    /// Parse the path as it is, which will most likely not match any target.
+    Reject,
+}
+
+impl Default for TrailingSlashPolicy {
+    fn default() -> Self {
+        Self::Allow
+    }
+}
+
+/// The way the router stores the target in the URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RouterMode {
+    /// Use the path of the URL, like `/users/42`.
+    Browser,
+    /// Use the fragment of the URL, like `#/users/42`.
+    ///
+    /// This doesn't require support from the server, as the path of the page stays the same. The
+    /// `<base>` element is not evaluated in this mode, as it only applies to the path of the
+    /// page. Also, scrolling to anchors is not supported.
+    Hash,
+    /// Keep the history in memory, not touching the URL of the browser.
+    ///
+    /// The history starts with the provided routes, the last one being the current entry. If no
+    /// routes are provided, it starts with the root route (`/`).
+    Memory(Vec<String>),
+}
+
+impl Default for RouterMode {
+    fn default() -> Self {
+        Self::Browser
+    }
+}
+
+impl RouterMode {
+    /// Create an in-memory history, restoring the entries acquired by
+    /// [`RouterContext::serialize_history`].
+    pub fn restore_from_history(entries: Vec<String>) -> Self {
+        Self::Memory(entries)
+    }
+}
+
+/// An error, reported when the current location can't be parsed into a target.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RouteError {
+    /// The raw path of the location.
+    pub path: String,
+    /// A description of why the path could not be parsed.
+    pub description: String,
+    /// The router will retry parsing the path after the provided number of milliseconds.
+    pub retry_after_ms: Option<u32>,
+}
+
+impl RouteError {
+    fn new(path: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            description: description.into(),
+            retry_after_ms: None,
+        }
+    }
 }
 
 #[derive(Debug)]
 #[doc(hidden)]
 pub enum Msg<T: Target> {
-    RouteChanged(Location),
+    RouteChanged(String),
     ChangeTarget(T),
+    ReplaceTarget(T),
+    ChangeTargetAnimated(T, RouteAnimation),
+    ChangeTargetTitled(T, String),
+    ChangeTargetWithQuery(T, String),
+    GoDefault,
+    GoUp,
+    GoHome,
+    GoRoot,
+    GoError(u16, String),
+    SetPending(Option<T>),
+}
+
+/// A point-in-time view of the state of a router, see [`use_router_snapshot`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouterSnapshot<T> {
+    /// The active target.
+    pub active_target: Option<T>,
+    /// The number of history entries before the current one, see [`RouterContext::entries_back`].
+    pub history_index: usize,
+    /// The time the snapshot was taken, in milliseconds since the epoch.
+    pub timestamp: f64,
+}
+
+/// The most recent snapshots of a router, oldest first, see [`use_router_snapshots`].
+///
+/// The router records a snapshot whenever the active target changes, keeping up to
+/// [`RouterSnapshots::LIMIT`] of them. This is only available in debug builds.
+#[cfg(debug_assertions)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouterSnapshots<T>(Rc<std::collections::VecDeque<RouterSnapshot<T>>>);
+
+#[cfg(debug_assertions)]
+impl<T> Default for RouterSnapshots<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+#[cfg(debug_assertions)]
+impl<T: Clone> RouterSnapshots<T> {
+    /// The maximum number of snapshots which are kept, dropping the oldest ones.
+    pub const LIMIT: usize = 50;
+
+    /// Get the snapshots, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &RouterSnapshot<T>> {
+        self.0.iter()
+    }
+
+    /// Get the most recent snapshot.
+    pub fn last(&self) -> Option<&RouterSnapshot<T>> {
+        self.0.back()
+    }
+
+    /// Get the number of snapshots.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check if there are no snapshots.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn record(&self, snapshot: RouterSnapshot<T>) -> Self {
+        let mut snapshots = (*self.0).clone();
+        if snapshots.len() >= Self::LIMIT {
+            snapshots.pop_front();
+        }
+        snapshots.push_back(snapshot);
+        Self(Rc::new(snapshots))
+    }
 }
 
 /// Top-level router component.
 pub struct Router<T: Target> {
-    history: AnyHistory,
-    _listener: HistoryListener,
+    history: RouterHistory,
+    _listener: RouterListener,
     target: Option<T>,
+    pending: Option<T>,
+    animation: Option<RouteAnimation>,
+    pending_animation: Option<RouteAnimation>,
+    pending_title: Option<String>,
 
     scope: Rc<ScopeContext<T>>,
     router: RouterContext<T>,
+    erased: ErasedRouterContext,
+    shared: SharedState<T>,
+    #[cfg(debug_assertions)]
+    snapshots: RouterSnapshots<T>,
 
     base: String,
+    retry: Option<Timeout>,
+    scroll_pending: bool,
+    mode: RouterMode,
+    depth: DepthContext,
+}
+
+/// The state of a router, which is shared by its contexts, and kept when they are built again.
+struct SharedState<T> {
+    visited: VisitedTargets<T>,
+    delayed: DelayedNavigations,
+}
+
+impl<T> Default for SharedState<T> {
+    fn default() -> Self {
+        Self {
+            visited: Default::default(),
+            delayed: Default::default(),
+        }
+    }
+}
+
+/// Render the route (the URL path) of a target, below the application base.
+fn render_route<T: Target>(base: &str, target: &T) -> String {
+    format!(
+        "{}/{}",
+        base,
+        target
+            .render_path()
+            .into_iter()
+            .map(|segment| urlencoding::encode(&segment).to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    )
+}
+
+/// Get the path segments of a target, without the single empty segment of an index target.
+///
+/// This allows to shorten or extend the path of an index target like any other path.
+fn segments_of<T: Target>(target: &T) -> Vec<String> {
+    let mut path = target.render_path();
+    if matches!(path.as_slice(), [segment] if segment.is_empty()) {
+        path.clear();
+    }
+    path
+}
+
+/// Check if the path is the same as, or a child of, the parent path, comparing whole segments.
+///
+/// An empty parent path, like the one of an index target, only matches an empty path, and not
+/// every path.
+fn is_same_or_below(path: &[String], parent: &[String]) -> bool {
+    match parent.is_empty() {
+        true => path.is_empty(),
+        false => path.starts_with(parent),
+    }
+}
+
+/// Get the path segments for parsing a target, using `[""]` for an empty path.
+///
+/// Index targets only parse from a single empty segment, like the ones of [`Msg::GoHome`].
+fn parse_segments(path: &[String]) -> Vec<&str> {
+    match path.is_empty() {
+        true => vec![""],
+        false => path.iter().map(|s| s.as_str()).collect(),
+    }
+}
+
+/// Get the current time, in milliseconds since the epoch.
+#[cfg(target_arch = "wasm32")]
+fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+/// Get the current time, in milliseconds since the epoch, e.g. for server-side rendering.
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs_f64() * 1000.0)
+        .unwrap_or_default()
+}
+
+/// Get the `sessionStorage`, if there is a window providing one.
+fn session_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.session_storage().ok().flatten()
+}
+
+/// The prefix of the key for persisting the target during development, followed by the type name
+/// of the target, see [`RouterProps::persist_to_session_storage`].
+const DEV_PERSIST_KEY: &str = "__yew_router_dev_path__";
+
+thread_local! {
+    /// The development keys, which were already restored since the page was loaded.
+    static DEV_RESTORED: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+
+    /// The callbacks for navigating globally, by the type of the target.
+    static GLOBAL_NAVIGATION: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+/// The nesting level of a routing context.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct DepthContext(pub(crate) usize);
+
+impl DepthContext {
+    pub(crate) fn nested(self) -> Self {
+        Self(self.0 + 1)
+    }
 }
 
 impl<T> Component for Router<T>
@@ -131,36 +1342,79 @@ where
     type Properties = RouterProps<T>;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let history = AnyHistory::Browser(BrowserHistory::new());
+        let mode = ctx.props().mode.clone();
+        let history = RouterHistory::new(
+            &mode,
+            ctx.props().max_history_length,
+            ctx.props().history_key.as_deref(),
+        );
 
         let cb = ctx.link().callback(Msg::RouteChanged);
 
-        let base = ctx
-            .props()
-            .base
-            .clone()
-            .or_else(|| base::eval_base())
-            .unwrap_or_else(|| "".into());
+        let base = Self::eval_base(ctx, &mode);
+
+        GLOBAL_NAVIGATION.with(|navigation| {
+            let mut navigation = navigation.borrow_mut();
+            if navigation.contains_key(&TypeId::of::<T>()) {
+                panic!("Only one router of the same target type may exist at a time");
+            }
+            let callback = ctx.link().callback(Msg::ChangeTarget);
+            navigation.insert(TypeId::of::<T>(), Box::new(callback));
+        });
 
-        let target =
-            Self::parse_location(&base, history.location()).or_else(|| ctx.props().default.clone());
+        let (target, retry) = match Self::restore_target(ctx) {
+            Some(target) => {
+                let route = render_route(&base, &target);
+                if history.path() != route {
+                    log::debug!("Restore URL: {route}");
+                    history.replace(route);
+                }
+                (Some(target), None)
+            }
+            None => Self::eval_target(ctx, &base, &history.path()),
+        };
 
         let listener = {
             let history = history.clone();
             history.clone().listen(move || {
-                cb.emit(history.location());
+                cb.emit(history.path());
             })
         };
 
-        let (scope, router) = Self::build_context(&target, ctx);
+        let shared = SharedState::default();
+        let (scope, router) =
+            Self::build_context(&history, &base, &target, &None, &None, &shared, ctx);
+        let erased = router.as_erased();
+        Self::update_title(ctx, &target);
+        Self::persist_target(ctx, &target);
+        #[cfg(debug_assertions)]
+        let snapshots = RouterSnapshots::default().record(router.snapshot());
+
+        let depth = ctx
+            .link()
+            .context::<DepthContext>(Callback::noop())
+            .map(|(depth, _)| depth.nested())
+            .unwrap_or(DepthContext(0));
 
         Self {
             history,
             _listener: listener,
             target,
+            pending: None,
+            animation: None,
+            pending_animation: None,
+            pending_title: None,
             scope,
             router,
+            erased,
+            shared,
+            #[cfg(debug_assertions)]
+            snapshots,
             base,
+            retry,
+            scroll_pending: ctx.props().anchor_scroll && mode == RouterMode::Browser,
+            mode,
+            depth,
         }
     }
 
@@ -168,106 +1422,504 @@ where
         // log::debug!("update: {msg:?}");
 
         match msg {
-            Msg::RouteChanged(location) => {
-                let target = Self::parse_location(&self.base, location)
-                    .or_else(|| ctx.props().default.clone());
-                if target != self.target {
+            Msg::RouteChanged(path) => {
+                let (target, retry) = Self::eval_target(ctx, &self.base, &path);
+                // replacing the timer cancels a previous retry
+                self.retry = retry;
+                self.scroll_pending = ctx.props().anchor_scroll && self.mode == RouterMode::Browser;
+                let animation = self.pending_animation.take();
+                // an explicit title overrides the one of the target
+                if let Some(title) = self.pending_title.take() {
+                    gloo_utils::document().set_title(&title);
+                } else if target != self.target {
+                    Self::update_title(ctx, &target);
+                }
+                // any navigation ends a pending transition
+                let pending = self.pending.take().is_some();
+                if target != self.target || pending {
+                    Self::persist_target(ctx, &target);
                     self.target = target;
+                    self.animation = animation;
                     self.sync_context(ctx);
+                    #[cfg(debug_assertions)]
+                    {
+                        self.snapshots = self.snapshots.record(self.router.snapshot());
+                    }
+                    if let Some(target) = &self.target {
+                        self.router.emit_navigate_event(target);
+                    }
                     return true;
                 }
+                // nothing to render, so the element is already there
+                self.scroll_to_anchor();
             }
             Msg::ChangeTarget(target) => {
-                // log::debug!("Pushing state: {:?}", request.path);
-                let route = format!(
-                    "{}/{}",
-                    self.base,
-                    target
-                        .render_path()
-                        .into_iter()
-                        .map(|segment| urlencoding::encode(&segment).to_string())
-                        .collect::<Vec<_>>()
-                        .join("/")
-                );
+                return self.push_target(ctx, &target);
+            }
+            Msg::ReplaceTarget(target) => {
+                if !Self::allowed(ctx, &target) {
+                    return self.refuse(ctx);
+                }
+                let route = self.render_route(&target);
+                log::debug!("Replace URL: {route}");
+                self.history.replace(route);
+            }
+            Msg::ChangeTargetAnimated(target, animation) => {
+                self.pending_animation = Some(animation);
+                return self.push_target(ctx, &target);
+            }
+            Msg::ChangeTargetTitled(target, title) => {
+                self.pending_title = Some(title);
+                return self.push_target(ctx, &target);
+            }
+            Msg::ChangeTargetWithQuery(target, query) => {
+                if !Self::allowed(ctx, &target) {
+                    return self.refuse(ctx);
+                }
+                let route = self.render_route(&target);
+                log::debug!("Push URL: {route}{query}");
+                self.history.push_with_query(route, &query);
+            }
+            Msg::GoDefault => match ctx.props().default.clone() {
+                Some(default) => return self.push_target(ctx, &default),
+                None => log::debug!("No default target configured"),
+            },
+            Msg::GoUp => {
+                let mut path = match &self.target {
+                    Some(target) => target.render_path(),
+                    None => {
+                        log::debug!("No active target to go up from");
+                        return false;
+                    }
+                };
+                path.pop();
+                let path = parse_segments(&path);
+                match T::parse_path_with_context(&path, &ctx.props().parse_context) {
+                    Some(target) => return self.push_target(ctx, &target),
+                    None => log::warn!("No target matches the parent path: {path:?}"),
+                }
+            }
+            Msg::GoHome => {
+                match T::parse_path_with_context(&[""], &ctx.props().parse_context)
+                    .or_else(|| ctx.props().default.clone())
+                {
+                    Some(target) => return self.push_target(ctx, &target),
+                    None => log::debug!("Neither the root path nor a default target is available"),
+                }
+            }
+            Msg::GoRoot => {
+                let route = format!("{}/", self.base);
                 log::debug!("Push URL: {route}");
                 self.history.push(route);
             }
+            Msg::GoError(code, message) => match &ctx.props().error_route {
+                Some(error_route) => {
+                    let target = error_route.emit((code, message));
+                    return self.push_target(ctx, &target);
+                }
+                None => log::warn!("No error route configured, ignoring error {code}: {message}"),
+            },
+            Msg::SetPending(target) => {
+                self.pending = target;
+                self.sync_context(ctx);
+                return true;
+            }
         }
 
         false
     }
 
-    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().base != old_props.base {
+            // the application base changed, e.g. when embedded into a different application
+            self.base = Self::eval_base(ctx, &self.mode);
+            let (target, retry) = Self::eval_target(ctx, &self.base, &self.history.path());
+            self.retry = retry;
+            Self::update_title(ctx, &target);
+            Self::persist_target(ctx, &target);
+            self.target = target;
+        }
         self.sync_context(ctx);
         true
     }
 
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        GLOBAL_NAVIGATION.with(|navigation| {
+            navigation.borrow_mut().remove(&TypeId::of::<T>());
+        });
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        self.scroll_to_anchor();
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let scope = self.scope.clone();
         let router = self.router.clone();
+        let erased = self.erased.clone();
+
+        let content = html! (
+            <ContextProvider<DepthContext> context={self.depth}>
+                <ContextProvider<ErasedRouterContext> context={erased}>
+                    <ContextProvider<ScopeContext<T>> context={(*scope).clone()}>
+                        <ContextProvider<RouterContext<T >> context={router}>
+                            { for ctx.props().children.iter() }
+                        </ContextProvider<RouterContext<T >>>
+                    </ContextProvider<ScopeContext<T>>>
+                </ContextProvider<ErasedRouterContext>>
+            </ContextProvider<DepthContext>>
+        );
+
+        // the recorded snapshots are only available in debug builds
+        #[cfg(debug_assertions)]
+        let content = html!(
+            <ContextProvider<RouterSnapshots<T>> context={self.snapshots.clone()}>
+                { content }
+            </ContextProvider<RouterSnapshots<T>>>
+        );
+
+        content
+    }
+}
 
-        html! (
-            <ContextProvider<ScopeContext<T>> context={(*scope).clone()}>
-                <ContextProvider<RouterContext<T >> context={router}>
-                    { for ctx.props().children.iter() }
-                </ContextProvider<RouterContext<T >>>
-            </ContextProvider<ScopeContext<T>>>
-        )
+impl<T: Target + 'static> Router<T> {
+    /// Push a new target to the history of the router of the type, outside of a component, e.g.
+    /// from a timer or a JavaScript callback.
+    ///
+    /// Only one router of a target type may exist at a time, creating a second one panics. If no
+    /// router of the type exists, a warning is logged instead.
+    pub fn navigate_globally(target: T) {
+        let callback = GLOBAL_NAVIGATION.with(|navigation| {
+            navigation
+                .borrow()
+                .get(&TypeId::of::<T>())
+                .and_then(|callback| callback.downcast_ref::<Callback<T>>())
+                .cloned()
+        });
+        match callback {
+            Some(callback) => callback.emit(target),
+            None => log::warn!("No router for navigating globally to {target:?}"),
+        }
     }
 }
 
 impl<T: Target> Router<T> {
-    fn parse_location(base: &str, location: Location) -> Option<T> {
-        // get the current path
-        let path = location.path();
+    /// Evaluate the application base, from the properties or the `<base>` element.
+    fn eval_base(ctx: &Context<Self>, mode: &RouterMode) -> String {
+        ctx.props()
+            .base
+            .clone()
+            .or_else(|| match mode {
+                RouterMode::Browser => base::eval_base(),
+                RouterMode::Hash | RouterMode::Memory(_) => None,
+            })
+            .unwrap_or_else(|| "".into())
+    }
+
+    /// Evaluate the target from the location, falling back to the default target.
+    ///
+    /// If neither works, the error is reported to the `on_error` and `on_not_found` callbacks.
+    /// Unless the target requested to retry parsing, in which case there is no target until the
+    /// returned timer fires.
+    fn eval_target(ctx: &Context<Self>, base: &str, path: &str) -> (Option<T>, Option<Timeout>) {
+        let policy = ctx.props().strict_trailing_slash.unwrap_or_default();
+        let trailing_slash = path
+            .strip_prefix(base)
+            .map(|local| local.len() > 1 && local.ends_with('/'))
+            .unwrap_or_default();
+        let stripped = match (policy, trailing_slash) {
+            (TrailingSlashPolicy::Allow | TrailingSlashPolicy::Redirect, true) => {
+                Some(&path[..path.len() - 1])
+            }
+            _ => None,
+        };
+
+        let target = match Self::parse_location(
+            base,
+            stripped.unwrap_or(path),
+            &ctx.props().parse_context,
+        ) {
+            Ok(target) => match target.canonical_path() {
+                Some(canonical) => {
+                    log::debug!("Redirect to the canonical target: {canonical:?}");
+                    ctx.link()
+                        .send_message(Msg::ReplaceTarget(canonical.clone()));
+                    Some(canonical)
+                }
+                None => {
+                    if stripped.is_some() && policy == TrailingSlashPolicy::Redirect {
+                        log::debug!("Redirect to the path without the trailing slash");
+                        ctx.link().send_message(Msg::ReplaceTarget(target.clone()));
+                    }
+                    Some(target)
+                }
+            },
+            Err(RouteError {
+                retry_after_ms: Some(millis),
+                ..
+            }) => {
+                log::debug!("Retry parsing the location in {millis} ms");
+                let link = ctx.link().clone();
+                let path = path.to_string();
+                let retry = Timeout::new(millis, move || {
+                    link.send_message(Msg::RouteChanged(path));
+                });
+                return (None, Some(retry));
+            }
+            Err(err) => match ctx.props().default.clone() {
+                Some(default) => Some(default),
+                None => {
+                    log::debug!("Unable to parse location: {err:?}");
+                    if let Some(on_not_found) = &ctx.props().on_not_found {
+                        on_not_found.emit(err.path.clone());
+                    }
+                    if let Some(on_error) = &ctx.props().on_error {
+                        on_error.emit(err);
+                    }
+                    None
+                }
+            },
+        };
+        (target, None)
+    }
+
+    fn parse_location(
+        base: &str,
+        path: &str,
+        parse_context: &ParseContext,
+    ) -> Result<T, RouteError> {
         // if the prefix doesn't match, nothing will
-        if !path.starts_with(&base) {
-            return None;
+        if !path.starts_with(base) {
+            return Err(RouteError::new(
+                path,
+                format!("Path doesn't start with the application base '{base}'"),
+            ));
         }
         // split off the prefix
-        let (_, path) = path.split_at(base.len());
-        log::debug!("Path: {path}");
-
-        // parse into path segments
-        let path: Result<Vec<Cow<str>>, _> = path
-            .split('/')
-            .skip(1)
-            // urldecode in the process
-            .map(|segment| urlencoding::decode(segment))
-            .collect();
-
-        // get a path, or return none if we had an urldecode error
-        let path = match &path {
-            Ok(path) => path.iter().map(|s| s.as_ref()).collect::<Vec<_>>(),
-            Err(_) => return None,
+        let (_, local) = path.split_at(base.len());
+        log::debug!("Path: {local}");
+
+        // parse into (urldecoded) path segments
+        let segments = split_path(local);
+
+        // get a path, or fail if we had an urldecode error
+        let segments = match &segments {
+            Ok(segments) => segments.iter().map(|s| s.as_ref()).collect::<Vec<_>>(),
+            Err(err) => {
+                return Err(RouteError::new(
+                    path,
+                    format!("Failed to decode path: {err}"),
+                ))
+            }
         };
 
         // parse the path into a target
-        log::debug!("Path: {path:?}");
-        let target = T::parse_path(&path);
+        log::debug!("Path: {segments:?}");
+        let target = T::try_parse_path(&segments, parse_context);
         log::debug!("New target: {target:?}");
 
         // done
-        target
+        target.map_err(|err| match err.retry_after_ms {
+            Some(_) => RouteError {
+                retry_after_ms: err.retry_after_ms,
+                ..RouteError::new(path, "Parsing the path failed, but may succeed later")
+            },
+            None => RouteError::new(path, "No target matches the path"),
+        })
+    }
+
+    fn update_title(ctx: &Context<Self>, target: &Option<T>) {
+        if !ctx.props().update_title {
+            return;
+        }
+        if let Some(title) = target.as_ref().and_then(|target| target.metadata().title) {
+            gloo_utils::document().set_title(&title);
+        }
+    }
+
+    /// The key for persisting the target, if it should be persisted.
+    fn persist_key(ctx: &Context<Self>) -> Option<String> {
+        if let Some(key) = &ctx.props().persist_key {
+            return Some(key.to_string());
+        }
+        let dev = cfg!(debug_assertions)
+            && cfg!(target_arch = "wasm32")
+            && ctx.props().persist_to_session_storage
+            && ctx.props().history_key.is_none()
+            && matches!(ctx.props().mode, RouterMode::Memory(_));
+        dev.then(|| format!("{DEV_PERSIST_KEY}:{}", std::any::type_name::<T>()))
+    }
+
+    /// Restore the persisted target, if there is one.
+    fn restore_target(ctx: &Context<Self>) -> Option<T> {
+        let key = Self::persist_key(ctx)?;
+        // during development, only restore after a reload, not when remounting the router
+        let dev = ctx.props().persist_key.is_none();
+        if dev && !DEV_RESTORED.with(|restored| restored.borrow_mut().insert(key.clone())) {
+            return None;
+        }
+        let storage = session_storage()?;
+        let path = storage.get_item(&key).ok().flatten()?;
+        T::parse_str(&path)
+    }
+
+    /// Persist the target, if it should be persisted.
+    fn persist_target(ctx: &Context<Self>, target: &Option<T>) {
+        let key = match Self::persist_key(ctx) {
+            Some(key) => key,
+            None => return,
+        };
+        let storage = match session_storage() {
+            Some(storage) => storage,
+            None => return,
+        };
+        let result = match target {
+            Some(target) => storage.set_item(&key, &RouterContext::path_string_for(target)),
+            None => storage.remove_item(&key),
+        };
+        if let Err(err) = result {
+            log::warn!("Failed to persist the target: {err:?}");
+        }
+    }
+
+    /// Render the route (the URL path) of a target.
+    fn render_route(&self, target: &T) -> String {
+        render_route(&self.base, target)
+    }
+
+    /// Check if the guards, provided by the context, allow navigating to the target.
+    fn allowed(ctx: &Context<Self>, target: &T) -> bool {
+        let allowed = ctx
+            .link()
+            .context::<RouterGuardContext<T>>(Callback::noop())
+            .map(|(guards, _)| guards.allows(target))
+            .unwrap_or(true);
+        if !allowed {
+            log::debug!("Navigation to {target:?} was refused by a guard");
+        }
+        allowed
+    }
+
+    /// Push the target to the history, if the guards allow it.
+    ///
+    /// Returns `true` if the component needs to be rendered again, because a refused navigation
+    /// ended a pending transition.
+    fn push_target(&mut self, ctx: &Context<Self>, target: &T) -> bool {
+        if !Self::allowed(ctx, target) {
+            return self.refuse(ctx);
+        }
+        let route = self.render_route(target);
+        log::debug!("Push URL: {route}");
+        self.history.push(route);
+        false
+    }
+
+    /// Reset the state prepared for a navigation, which was refused by a guard.
+    ///
+    /// Returns `true` if the component needs to be rendered again, because this ended a pending
+    /// transition.
+    fn refuse(&mut self, ctx: &Context<Self>) -> bool {
+        self.pending_animation = None;
+        self.pending_title = None;
+        if self.pending.take().is_none() {
+            return false;
+        }
+        self.sync_context(ctx);
+        true
+    }
+
+    /// Scroll to the element referenced by the fragment, if a scroll is pending.
+    fn scroll_to_anchor(&mut self) {
+        if !std::mem::take(&mut self.scroll_pending) {
+            return;
+        }
+
+        let hash = gloo_utils::window().location().hash().unwrap_or_default();
+        let id = match hash.strip_prefix('#') {
+            Some(id) if !id.is_empty() => urlencoding::decode(id)
+                .map(|id| id.to_string())
+                .unwrap_or_else(|_| id.to_string()),
+            _ => return,
+        };
+
+        log::debug!("Scroll to anchor: {id}");
+        if let Some(element) = gloo_utils::document().get_element_by_id(&id) {
+            element.scroll_into_view();
+        }
     }
 
     fn sync_context(&mut self, ctx: &Context<Self>) {
-        let (scope, router) = Self::build_context(&self.target, ctx);
+        let (scope, router) = Self::build_context(
+            &self.history,
+            &self.base,
+            &self.target,
+            &self.pending,
+            &self.animation,
+            &self.shared,
+            ctx,
+        );
+        self.erased = router.as_erased();
         self.scope = scope;
         self.router = router;
     }
 
     fn build_context(
+        history: &RouterHistory,
+        base: &str,
         target: &Option<T>,
+        pending: &Option<T>,
+        animation: &Option<RouteAnimation>,
+        shared: &SharedState<T>,
         ctx: &Context<Self>,
     ) -> (Rc<ScopeContext<T>>, RouterContext<T>) {
+        if let Some(target) = target {
+            shared.visited.visit(target);
+        }
+
         let scope = Rc::new(ScopeContext {
-            upwards: ctx.link().callback(Msg::ChangeTarget),
+            upwards: ctx.link().callback(|navigation| match navigation {
+                Navigation::Push(target) => Msg::ChangeTarget(target),
+                Navigation::Replace(target) => Msg::ReplaceTarget(target),
+                Navigation::Animated(target, animation) => {
+                    Msg::ChangeTargetAnimated(target, animation)
+                }
+                Navigation::Titled(target, title) => Msg::ChangeTargetTitled(target, title),
+                Navigation::WithQuery(target, query) => Msg::ChangeTargetWithQuery(target, query),
+                Navigation::Default => Msg::GoDefault,
+                Navigation::Up => Msg::GoUp,
+                Navigation::Home => Msg::GoHome,
+                Navigation::Root => Msg::GoRoot,
+                Navigation::Error(code, message) => Msg::GoError(code, message),
+                Navigation::Pending(target) => Msg::SetPending(target),
+            }),
+            history: history.clone(),
+            route: {
+                let base = base.to_string();
+                let history = history.clone();
+                Callback::from(move |target: T| history.href(render_route(&base, &target)))
+            },
+            allowed: {
+                let guards = ctx
+                    .link()
+                    .context::<RouterGuardContext<T>>(Callback::noop())
+                    .map(|(guards, _)| guards);
+                Callback::from(move |target: T| {
+                    guards
+                        .as_ref()
+                        .map(|guards| guards.allows(&target))
+                        .unwrap_or(true)
+                })
+            },
+            default: ctx.props().default.clone(),
+            visited: shared.visited.clone(),
+            delayed: shared.delayed.clone(),
         });
 
         let router = RouterContext {
             scope: scope.clone(),
             active_target: target.clone(),
+            pending_target: pending.clone(),
+            animation: animation.clone(),
         };
 
         (scope, router)
@@ -285,3 +1937,208 @@ where
 {
     use_context()
 }
+
+#[hook]
+/// Get the named values of the active target, like `id` of `User { id: String }`.
+///
+/// This includes the values of all nested targets. Outside a router of the type `T`, or without
+/// an active target, the map is empty.
+pub fn use_match_groups<T>() -> HashMap<String, String>
+where
+    T: Target + 'static,
+{
+    use_router::<T>()
+        .map(|router| router.path_parameters())
+        .unwrap_or_default()
+}
+
+#[hook]
+/// Synchronize external state with the active target.
+///
+/// The function gets called with the active target whenever it changes, and with a callback
+/// for navigating to a different target, e.g. to redirect based on external state. Outside a
+/// router of the type `T`, the function is never called.
+pub fn use_router_sync<T, F>(sync_fn: F)
+where
+    T: Target + 'static,
+    F: Fn(Option<&T>, &mut dyn FnMut(T)) + 'static,
+{
+    let router = use_router::<T>();
+    let active = router.as_ref().map(|router| router.active().clone());
+
+    use_effect_with_deps(
+        move |active| {
+            if let (Some(router), Some(active)) = (router, active) {
+                sync_fn(active.as_ref(), &mut |target| router.push(target));
+            }
+        },
+        active,
+    );
+}
+
+#[hook]
+/// Run an effect after each change of the active target.
+///
+/// The effect gets called with the active target, and returns a destructor, which runs before
+/// the effect runs again, or when the component is removed. Outside a router of the type `T`, the
+/// effect only runs once, without an active target.
+pub fn use_router_effect<T, F, D>(effect: F)
+where
+    T: Target + 'static,
+    F: FnOnce(Option<&T>) -> D + 'static,
+    D: FnOnce() + 'static,
+{
+    let active = use_router::<T>().and_then(|router| router.active_target);
+
+    use_effect_with_deps(move |active| effect(active.as_ref()), active);
+}
+
+#[hook]
+/// Select a value derived from the router, which is only computed again when the router changes.
+///
+/// The value is computed during the render, so it always matches the current state of the
+/// router. Rendering the same router state again reuses the previously selected value.
+///
+/// # Panics
+///
+/// Panics if it is not called from a component nested into a [`Router`] of the type `T`.
+pub fn use_router_selector<T, R, F>(selector: F) -> R
+where
+    T: Target + 'static,
+    R: Clone + 'static,
+    F: FnOnce(&RouterContext<T>) -> R,
+{
+    let router = use_router::<T>().expect("Need Router or Nested component");
+    (*use_memo(move |router| selector(router), router)).clone()
+}
+
+#[hook]
+/// Check if the active target matches the predicate, for custom active states.
+///
+/// Outside a router of the type `T`, or without an active target, this returns `false`.
+pub fn use_router_matches<T, F>(predicate: F) -> bool
+where
+    T: Target + 'static,
+    F: Fn(&T) -> bool,
+{
+    use_router::<T>()
+        .and_then(|router| router.active_target)
+        .map(|target| predicate(&target))
+        .unwrap_or_default()
+}
+
+#[hook]
+/// Get a single segment of the path of the active target.
+///
+/// The index is relative to the target of type `T`. Outside a router of the type `T`, without an
+/// active target, or if the path has fewer segments, this returns [`None`].
+pub fn use_active_segment<T>(index: usize) -> Option<String>
+where
+    T: Target + 'static,
+{
+    use_router::<T>()
+        .and_then(|router| router.active_target)
+        .and_then(|target| target.render_path().into_iter().nth(index))
+}
+
+#[hook]
+/// Get a snapshot of the state of the router, for time-travel debugging.
+///
+/// The snapshot can later be passed to [`RouterContext::restore_snapshot`]. In debug builds, the
+/// router also records the most recent snapshots, see [`use_router_snapshots`]. Outside a router
+/// of the type `T`, this returns [`None`].
+pub fn use_router_snapshot<T>() -> Option<RouterSnapshot<T>>
+where
+    T: Target + 'static,
+{
+    use_router::<T>().map(|router| router.snapshot())
+}
+
+#[cfg(debug_assertions)]
+#[hook]
+/// Get the most recent snapshots of the router, recorded whenever its active target changed, for
+/// time-travel debugging.
+///
+/// This is only available in debug builds. Outside a router of the type `T`, this returns
+/// [`None`].
+pub fn use_router_snapshots<T>() -> Option<RouterSnapshots<T>>
+where
+    T: Target + 'static,
+{
+    use_context::<RouterSnapshots<T>>()
+}
+
+#[hook]
+/// Get the nesting level of the current routing context.
+///
+/// The top-level [`Router`] has a depth of `0`, each nested [`Router`] or [`crate::Scope`]
+/// increases it by one. Outside a router, this returns `0` as well.
+pub fn use_router_depth() -> usize {
+    use_context::<DepthContext>()
+        .map(|depth| depth.0)
+        .unwrap_or_default()
+}
+
+/// The attributes of a link to a target, for rendering a custom link element, created by
+/// [`RouterContext::get_link_props`].
+///
+/// This isn't named `LinkProps`, to not clash with the properties of the
+/// [`crate::components::Link`] component.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkAttributes {
+    /// The URL of the target.
+    pub href: String,
+    /// The click handler, navigating to the target.
+    ///
+    /// Clicks using a modifier key, or another than the left button, are left to the browser, e.g.
+    /// for opening the link in a new tab. The default action is only prevented for `<a>`
+    /// elements, so other elements, like a submit button, keep theirs.
+    pub onclick: Callback<MouseEvent>,
+    /// If the target, or one of its children, is the active target.
+    pub is_active: bool,
+    /// If the target is the active target.
+    pub is_exact: bool,
+}
+
+/// A change of the active target, passed to the reducer of [`use_router_state`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RouteChange<T> {
+    /// The previously active target.
+    pub from: Option<T>,
+    /// The newly active target.
+    pub to: Option<T>,
+}
+
+#[hook]
+/// Keep state which is derived from the navigation, like an open panel which closes when the
+/// route changes.
+///
+/// The state is created using `init` on the first render, and the reducer gets called with the
+/// current state whenever the active target changes, returning the new state. Outside a router of
+/// the type `T`, there is no active target and the state never changes.
+pub fn use_router_state<T, S, I, R>(init: I, reducer: R) -> S
+where
+    T: Target + 'static,
+    S: Clone + 'static,
+    I: Fn() -> S,
+    R: Fn(S, RouteChange<T>) -> S,
+{
+    let router = use_router::<T>();
+    let active = router.and_then(|router| router.active_target);
+
+    let state = use_mut_ref(|| None::<(Option<T>, S)>);
+    let mut state = state.borrow_mut();
+
+    let next = match state.take() {
+        None => (active, init()),
+        Some((from, current)) if from == active => (from, current),
+        Some((from, current)) => {
+            let to = active.clone();
+            (active, reducer(current, RouteChange { from, to }))
+        }
+    };
+
+    let result = next.1.clone();
+    *state = Some(next);
+    result
+}
@@ -1,13 +1,162 @@
-use crate::router::RouterContext;
+use crate::history::RouterHistory;
+use crate::router::{DepthContext, RouterContext};
 use crate::target::{Mapper, Target};
+use crate::transition::RouteAnimation;
+use gloo_timers::callback::Timeout;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
 use yew::prelude::*;
 
+/// A navigation request, passed upwards through the scopes to the router.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Navigation<T> {
+    /// Push a new target.
+    Push(T),
+    /// Replace the current history entry with a new target.
+    Replace(T),
+    /// Push a new target, using an animation for the transition.
+    Animated(T, RouteAnimation),
+    /// Push a new target, setting the title of the document.
+    Titled(T, String),
+    /// Push a new target, with a query string.
+    WithQuery(T, String),
+    /// Push the default target of the router.
+    Default,
+    /// Push the target of the current path, minus its last segment.
+    Up,
+    /// Push the target of the root path.
+    Home,
+    /// Push the root path, without resolving a target.
+    Root,
+    /// Push the error target of the router, for the status code and message.
+    Error(u16, String),
+    /// Set (or clear) the target of a pending transition.
+    Pending(Option<T>),
+}
+
+impl<T> Navigation<T> {
+    /// Translate the target of the request.
+    pub(crate) fn map<U, F>(self, f: F) -> Navigation<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            Self::Push(target) => Navigation::Push(f(target)),
+            Self::Replace(target) => Navigation::Replace(f(target)),
+            Self::Animated(target, animation) => Navigation::Animated(f(target), animation),
+            Self::Titled(target, title) => Navigation::Titled(f(target), title),
+            Self::WithQuery(target, query) => Navigation::WithQuery(f(target), query),
+            Self::Default => Navigation::Default,
+            Self::Up => Navigation::Up,
+            Self::Home => Navigation::Home,
+            Self::Root => Navigation::Root,
+            Self::Error(code, message) => Navigation::Error(code, message),
+            Self::Pending(target) => Navigation::Pending(target.map(f)),
+        }
+    }
+}
+
+/// The last visited targets, by the path prefixes of the targets.
+#[derive(Clone, Debug)]
+pub(crate) struct VisitedTargets<T>(Rc<RefCell<HashMap<Vec<String>, T>>>);
+
+impl<T> Default for VisitedTargets<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> PartialEq for VisitedTargets<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: Target> VisitedTargets<T> {
+    /// Record the target as last visited child of all of its path prefixes.
+    pub(crate) fn visit(&self, target: &T) {
+        let path = target.render_path();
+        let mut visited = self.0.borrow_mut();
+        for n in 0..=path.len() {
+            visited.insert(path[..n].to_vec(), target.clone());
+        }
+    }
+
+    /// Get the last visited target below the path of the root target.
+    pub(crate) fn last_child(&self, root: &T) -> Option<T> {
+        self.0.borrow().get(&root.render_path()).cloned()
+    }
+}
+
+/// A navigation scheduled to happen after a delay.
+struct DelayedNavigation {
+    fired: Rc<Cell<bool>>,
+    /// Dropping the timer cancels the navigation.
+    _timeout: Timeout,
+}
+
+/// Navigations scheduled to happen after a delay.
+///
+/// Pending navigations are cancelled once the router, and all of its contexts, are dropped.
+#[derive(Clone, Default)]
+pub(crate) struct DelayedNavigations(Rc<RefCell<Vec<DelayedNavigation>>>);
+
+impl PartialEq for DelayedNavigations {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Debug for DelayedNavigations {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DelayedNavigations")
+            .field(&self.0.borrow().len())
+            .finish()
+    }
+}
+
+impl DelayedNavigations {
+    /// Run the function after the delay.
+    pub(crate) fn schedule<F>(&self, delay_ms: u32, f: F)
+    where
+        F: FnOnce() + 'static,
+    {
+        let mut pending = self.0.borrow_mut();
+        // drop the timers which already fired
+        pending.retain(|navigation| !navigation.fired.get());
+
+        let fired = Rc::new(Cell::new(false));
+        let timeout = {
+            let fired = fired.clone();
+            Timeout::new(delay_ms, move || {
+                fired.set(true);
+                f();
+            })
+        };
+        pending.push(DelayedNavigation {
+            fired,
+            _timeout: timeout,
+        });
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ScopeContext<C>
 where
     C: Target,
 {
-    pub(crate) upwards: Callback<C>,
+    pub(crate) upwards: Callback<Navigation<C>>,
+    pub(crate) history: RouterHistory,
+    /// Render the URL of a target, including the path of the parents.
+    pub(crate) route: Callback<C, String>,
+    /// Check if the guards of the router allow navigating to a target.
+    pub(crate) allowed: Callback<C, bool>,
+    pub(crate) default: Option<C>,
+    pub(crate) visited: VisitedTargets<C>,
+    pub(crate) delayed: DelayedNavigations,
 }
 
 impl<C> ScopeContext<C>
@@ -15,7 +164,70 @@ where
     C: Target,
 {
     pub(crate) fn push(&self, target: C) {
-        self.upwards.emit(target);
+        self.upwards.emit(Navigation::Push(target));
+    }
+
+    pub(crate) fn replace(&self, target: C) {
+        self.upwards.emit(Navigation::Replace(target));
+    }
+
+    pub(crate) fn push_animated(&self, target: C, animation: RouteAnimation) {
+        self.upwards.emit(Navigation::Animated(target, animation));
+    }
+
+    pub(crate) fn push_titled(&self, target: C, title: String) {
+        self.upwards.emit(Navigation::Titled(target, title));
+    }
+
+    pub(crate) fn push_with_query(&self, target: C, query: String) {
+        self.upwards.emit(Navigation::WithQuery(target, query));
+    }
+
+    pub(crate) fn go_default(&self) {
+        self.upwards.emit(Navigation::Default);
+    }
+
+    pub(crate) fn go_up(&self) {
+        self.upwards.emit(Navigation::Up);
+    }
+
+    pub(crate) fn go_home(&self) {
+        self.upwards.emit(Navigation::Home);
+    }
+
+    pub(crate) fn go_root(&self) {
+        self.upwards.emit(Navigation::Root);
+    }
+
+    pub(crate) fn go_error(&self, code: u16, message: String) {
+        self.upwards.emit(Navigation::Error(code, message));
+    }
+
+    pub(crate) fn set_pending(&self, target: Option<C>) {
+        self.upwards.emit(Navigation::Pending(target));
+    }
+
+    pub(crate) fn replace_query(&self, query: HashMap<String, String>) {
+        self.history.replace_query(query);
+    }
+
+    pub(crate) fn replace_state(&self, state: JsValue) {
+        self.history.replace_state(state);
+    }
+
+    pub(crate) fn state(&self) -> Option<JsValue> {
+        self.history.state()
+    }
+
+    pub(crate) fn push_delayed(&self, target: C, delay_ms: u32, replace: bool) {
+        // only capture the callback, as the context owns the timer
+        let upwards = self.upwards.clone();
+        self.delayed.schedule(delay_ms, move || {
+            upwards.emit(match replace {
+                true => Navigation::Replace(target),
+                false => Navigation::Push(target),
+            })
+        });
     }
 }
 
@@ -44,39 +256,105 @@ where
     let parent = use_context::<ScopeContext<P>>()
         .expect("Must be nested under a Router or Nested component of the parent type");
 
+    let depth = use_context::<DepthContext>()
+        .map(DepthContext::nested)
+        .unwrap_or(DepthContext(0));
+
     let Mapper { downwards, upwards } = props.mapper.emit(());
 
+    let visited = use_state(VisitedTargets::<C>::default);
+
     let scope = use_memo(
-        |(parent, upwards)| {
+        |(parent, upwards, downwards, visited)| {
+            let history = parent.history.clone();
+            let default = parent.default.clone().and_then(|p| downwards.emit(p));
+            let route = {
+                let parent = parent.route.clone();
+                let upwards = upwards.clone();
+                Callback::from(move |child: C| parent.emit(upwards.emit(child)))
+            };
+            let allowed = {
+                let parent = parent.allowed.clone();
+                let upwards = upwards.clone();
+                Callback::from(move |child: C| parent.emit(upwards.emit(child)))
+            };
+            let delayed = parent.delayed.clone();
             let parent = parent.upwards.clone();
             let upwards = upwards.clone();
             ScopeContext {
-                upwards: Callback::from(move |child: C| {
-                    parent.emit(upwards.emit(child));
+                upwards: Callback::from(move |child: Navigation<C>| {
+                    parent.emit(child.map(|child| upwards.emit(child)));
                 }),
+                history,
+                route,
+                allowed,
+                default,
+                visited: visited.clone(),
+                delayed,
             }
         },
-        (parent.clone(), upwards),
+        (
+            parent.clone(),
+            upwards,
+            downwards.clone(),
+            (*visited).clone(),
+        ),
     );
 
-    let active = router.active();
+    let active = router.active().clone().and_then(|p| downwards.emit(p));
+    if let Some(active) = &active {
+        visited.visit(active);
+    }
 
     let context = use_memo(
-        |(scope, target)| RouterContext {
+        |(scope, target, pending, animation)| RouterContext {
             scope: scope.clone(),
             active_target: target.clone(),
+            pending_target: pending.clone(),
+            animation: animation.clone(),
         },
         (
             scope.clone(),
-            active.clone().and_then(|p| downwards.emit(p)),
+            active,
+            router.pending().cloned().and_then(|p| downwards.emit(p)),
+            router.animation.clone(),
         ),
     );
 
     html!(
-        <ContextProvider<RouterContext<C>> context={(*context).clone()}>
-            <ContextProvider<ScopeContext<C>> context={(*scope).clone()}>
-                { for props.children.iter() }
-            </ContextProvider<ScopeContext<C>>>
-        </ContextProvider<RouterContext<C>>>
+        <ContextProvider<DepthContext> context={depth}>
+            <ContextProvider<RouterContext<C>> context={(*context).clone()}>
+                <ContextProvider<ScopeContext<C>> context={(*scope).clone()}>
+                    { for props.children.iter() }
+                </ContextProvider<ScopeContext<C>>>
+            </ContextProvider<RouterContext<C>>>
+        </ContextProvider<DepthContext>>
     )
 }
+
+/// A component, rendering its children in a [`Scope`], only if the active target maps to the
+/// child target type.
+///
+/// The parent target is selected using the mapper, like `Page::mapper_details` for a
+/// `Page::Details(Details)` variant. In contrast to [`Scope`], the children are not rendered if
+/// another parent target is active.
+#[function_component(NestedRoute)]
+pub fn nested_route<P, C>(props: &ScopeProps<P, C>) -> Html
+where
+    P: Target + 'static,
+    C: Target + 'static,
+{
+    let router = use_context::<RouterContext<P>>()
+        .expect("Must be nested under a Router or Nested component of the parent type");
+
+    let Mapper { downwards, .. } = props.mapper.emit(());
+
+    match router.active().clone().and_then(|p| downwards.emit(p)) {
+        Some(_) => html!(
+            <Scope<P, C> mapper={props.mapper.clone()}>
+                { for props.children.iter() }
+            </Scope<P, C>>
+        ),
+        None => html!(),
+    }
+}
@@ -0,0 +1,99 @@
+use crate::erased::ErasedRouterContext;
+use crate::history::RouterHistory;
+use crate::router::{DepthContext, RouterContext, RouterMode};
+use crate::scope::{DelayedNavigations, ScopeContext, VisitedTargets};
+use crate::target::Target;
+use std::rc::Rc;
+use yew::prelude::*;
+
+/// Properties for the [`StaticRouter`] component.
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct StaticRouterProps<T>
+where
+    T: Target,
+{
+    /// The content to render.
+    #[prop_or_default]
+    pub children: Children,
+
+    /// The path to render, like `/users/42`.
+    #[prop_or_default]
+    pub path: Option<AttrValue>,
+
+    /// The target to render. This takes precedence over the `path`.
+    #[prop_or_default]
+    pub target: Option<T>,
+
+    /// The default target to use in case none matched.
+    #[prop_or_default]
+    pub default: Option<T>,
+}
+
+/// A router, rendering a fixed target, e.g. for server-side rendering or snapshot tests.
+///
+/// In contrast to a [`crate::Router`] using the [`RouterMode::Memory`] mode, it doesn't manage a
+/// history. It never listens to history events, and navigation requests are ignored.
+#[function_component(StaticRouter)]
+pub fn static_router<T>(props: &StaticRouterProps<T>) -> Html
+where
+    T: Target + 'static,
+{
+    let depth = use_context::<DepthContext>()
+        .map(DepthContext::nested)
+        .unwrap_or(DepthContext(0));
+
+    let (scope, router) = (*use_memo(
+        |(path, target, default)| {
+            let path = path.as_deref().unwrap_or("/");
+            let target = target
+                .clone()
+                .or_else(|| T::parse_str(path))
+                .or_else(|| default.clone());
+
+            let scope = Rc::new(ScopeContext {
+                upwards: Callback::from(|navigation| {
+                    log::debug!("Ignoring navigation of a static router: {navigation:?}");
+                }),
+                history: RouterHistory::new(
+                    &RouterMode::Memory(vec![path.to_string()]),
+                    None,
+                    None,
+                ),
+                route: Callback::from(|target: T| RouterContext::path_string_for(&target)),
+                allowed: Callback::from(|_| true),
+                default: default.clone(),
+                visited: VisitedTargets::default(),
+                delayed: DelayedNavigations::default(),
+            });
+
+            let router = RouterContext {
+                scope: scope.clone(),
+                active_target: target,
+                pending_target: None,
+                animation: None,
+            };
+
+            (scope, router)
+        },
+        (
+            props.path.clone(),
+            props.target.clone(),
+            props.default.clone(),
+        ),
+    ))
+    .clone();
+
+    let erased = router.as_erased();
+
+    html!(
+        <ContextProvider<DepthContext> context={depth}>
+            <ContextProvider<ErasedRouterContext> context={erased}>
+                <ContextProvider<ScopeContext<T>> context={(*scope).clone()}>
+                    <ContextProvider<RouterContext<T>> context={router}>
+                        { for props.children.iter() }
+                    </ContextProvider<RouterContext<T>>>
+                </ContextProvider<ScopeContext<T>>>
+            </ContextProvider<ErasedRouterContext>>
+        </ContextProvider<DepthContext>>
+    )
+}
@@ -1,6 +1,15 @@
 //! Routing target
 
-use std::fmt::Debug;
+use std::any::Any;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::string::FromUtf8Error;
 use yew::Callback;
 
 /// A target for used by a router.
@@ -25,10 +34,338 @@ pub trait Target: Clone + Debug + Eq + 'static {
     /// Render the full path downwards.
     fn render_path_into(&self, path: &mut Vec<String>);
 
+    /// Get the named values of the full path, including our children.
+    fn captures(&self) -> HashMap<String, String> {
+        let mut captures = HashMap::new();
+        self.captures_into(&mut captures);
+        captures
+    }
+
+    /// Collect the named values of the full path downwards.
+    ///
+    /// By default, there are no named values.
+    fn captures_into(&self, _captures: &mut HashMap<String, String>) {}
+
+    /// Create a copy of the target, replacing the named values with the provided ones.
+    ///
+    /// Named values which are not part of the map are kept. Returns [`None`] if a value can't be
+    /// parsed. By default, there are no named values, and so the target is kept as it is.
+    fn with_captures(&self, _captures: &HashMap<String, String>) -> Option<Self> {
+        Some(self.clone())
+    }
+
     /// Parse the target from the provided (segmented) path.
     ///
     /// The path will be the local path, with the prefix already removed.
     fn parse_path(path: &[&str]) -> Option<Self>;
+
+    /// Parse the target from the provided (segmented) path, using additional context.
+    ///
+    /// This allows to take information into account, which is not part of the path, like
+    /// permissions or feature flags. By default, it ignores the context and delegates to
+    /// [`Target::parse_path`].
+    fn parse_path_with_context(path: &[&str], _ctx: &ParseContext) -> Option<Self> {
+        Self::parse_path(path)
+    }
+
+    /// Try parsing the target from the provided (segmented) path, using additional context.
+    ///
+    /// This is used by the router, allowing to report a transient failure, like data which
+    /// affects parsing not being loaded yet. In this case, the error can request the router to
+    /// retry later. By default, it delegates to [`Target::parse_path_with_context`] and never
+    /// requests a retry.
+    fn try_parse_path(path: &[&str], ctx: &ParseContext) -> Result<Self, ParseError> {
+        Self::parse_path_with_context(path, ctx).ok_or_else(ParseError::default)
+    }
+
+    /// Parse the target from the start of the provided (segmented) path, returning the target
+    /// and the remaining segments, which it didn't consume.
+    ///
+    /// This allows to incrementally parse a path, handing the remaining segments down to another
+    /// level. By default, this tries [`Target::parse_path`] with the longest prefix first.
+    fn resolve_child<'a>(path: &'a [&'a str]) -> Option<(Self, &'a [&'a str])> {
+        (0..=path.len())
+            .rev()
+            .find_map(|n| Self::parse_path(&path[..n]).map(|target| (target, &path[n..])))
+    }
+
+    /// Get all targets which can be enumerated, as they don't carry any values.
+    ///
+    /// Targets with values, like `User { id: String }`, are not included. By default, this is
+    /// empty.
+    fn children() -> Vec<Self> {
+        vec![]
+    }
+
+    /// Get the canonical form of this target, if this one isn't.
+    ///
+    /// When navigating to a target, which is not canonical, the router replaces it with the
+    /// canonical target. By default, all targets are canonical and [`None`] is returned.
+    fn canonical_path(&self) -> Option<Self> {
+        None
+    }
+
+    /// Check if this is the index target, the one matching when no further path segment is
+    /// present.
+    ///
+    /// By default, no target is the index target.
+    fn is_index(&self) -> bool {
+        false
+    }
+
+    /// Check if the target accepts the remaining segments of a path, following its own ones.
+    ///
+    /// Accepting them allows a nested router to handle them. By default, and when using the
+    /// derive macro without `#[target(subroutes)]`, remaining segments are rejected. Using
+    /// `#[target(subroutes = "path::to_fn")]`, they are checked by a `fn(&[&str]) -> bool`.
+    fn accepts_subroute(&self, _remaining: &[&str]) -> bool {
+        false
+    }
+
+    /// A hint of how long it takes to load the content of the target, in milliseconds.
+    ///
+    /// This can be used to prioritize prefetching targets, loading heavier ones first. By default,
+    /// and when using the derive macro without `#[target(load_hint = N)]`, this is `0`, meaning
+    /// "unknown".
+    fn estimated_load_time_ms(&self) -> u32 {
+        0
+    }
+
+    /// Additional, application specific, information about the target.
+    ///
+    /// By default, there is no information.
+    fn metadata(&self) -> RouteMetadata {
+        RouteMetadata::default()
+    }
+
+    /// A name of the target, for displaying it to the user, e.g. in breadcrumbs.
+    ///
+    /// By default, this is the title of its [`Target::metadata`], or the last segment of its path.
+    /// For a target without a path, like the root, and without a title, this is empty.
+    fn display_name(&self) -> String {
+        self.metadata()
+            .title
+            .or_else(|| self.render_path().pop())
+            .unwrap_or_default()
+    }
+
+    /// Describe the structure of the targets, e.g. for generating documentation.
+    ///
+    /// The derive macro generates this. By default, the schema is empty.
+    fn schema() -> RouteSchema {
+        RouteSchema::default()
+    }
+
+    /// Parse the target from a (local) URL path, like `/users/42`.
+    ///
+    /// The path must start with a slash (`/`). A query string or fragment will be ignored.
+    fn parse_str(path: &str) -> Option<Self> {
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let segments = split_path(path).ok()?;
+        let segments = segments.iter().map(|s| s.as_ref()).collect::<Vec<_>>();
+        Self::parse_path(&segments)
+    }
+
+    /// Match an HTTP request to a target, e.g. for server-side rendering.
+    ///
+    /// By default, the method is ignored and the path is parsed using [`Target::parse_str`].
+    /// Targets which only match specific methods can override this.
+    fn from_request(_method: &str, path: &str) -> Option<Self> {
+        Self::parse_str(path)
+    }
+}
+
+/// Compare two targets by their paths.
+///
+/// This lexicographically compares the rendered path segments, providing a stable ordering,
+/// without requiring the target to implement [`Ord`].
+pub fn path_ord<T: Target>(a: &T, b: &T) -> Ordering {
+    a.render_path().cmp(&b.render_path())
+}
+
+/// Split a (local) URL path into its URL decoded segments.
+///
+/// The path is expected to start with a slash (`/`), so the first (empty) segment is skipped.
+pub fn split_path(path: &str) -> Result<Vec<Cow<'_, str>>, FromUtf8Error> {
+    path.split('/').skip(1).map(urlencoding::decode).collect()
+}
+
+/// An error, returned by [`Target::try_parse_path`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseError {
+    /// Request the router to retry parsing after the provided number of milliseconds.
+    pub retry_after_ms: Option<u32>,
+}
+
+impl ParseError {
+    /// Create an error, requesting the router to retry parsing after the provided number of
+    /// milliseconds.
+    pub fn retry_after(millis: u32) -> Self {
+        Self {
+            retry_after_ms: Some(millis),
+        }
+    }
+}
+
+/// A target, wrapped for being used as a property of a component.
+///
+/// It dereferences to the target, and can be created from it using [`From`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RouteProperty<T: Target>(pub T);
+
+impl<T: Target> RouteProperty<T> {
+    /// Get the wrapped target.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Target> Deref for RouteProperty<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Target> From<T> for RouteProperty<T> {
+    fn from(target: T) -> Self {
+        Self(target)
+    }
+}
+
+/// Build a target from path segments, e.g. from runtime strings.
+///
+/// ```
+/// # use yew_nested_router::prelude::*;
+/// #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// pub enum AppRoute {
+///   User { id: u32 },
+/// }
+///
+/// let target = RouteBuilder::<AppRoute>::new().segment("user").segment(42).build();
+/// assert_eq!(target, Some(AppRoute::User { id: 42 }));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RouteBuilder<T> {
+    segments: Vec<String>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Default for RouteBuilder<T> {
+    fn default() -> Self {
+        Self {
+            segments: vec![],
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Target> RouteBuilder<T> {
+    /// Create a new builder, without any segments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a path segment.
+    pub fn segment(mut self, segment: impl ToString) -> Self {
+        self.segments.push(segment.to_string());
+        self
+    }
+
+    /// Parse the target from the segments, using [`Target::parse_path`].
+    pub fn build(&self) -> Option<T> {
+        let segments = self.segments.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        T::parse_path(&segments)
+    }
+}
+
+/// Information about a target, provided by [`Target::metadata`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RouteMetadata {
+    /// The title of the page.
+    ///
+    /// If enabled, the router will use this as the document title.
+    pub title: Option<String>,
+    /// Permissions required to access the target.
+    pub permissions: Vec<String>,
+    /// The name of the target, when reporting to analytics.
+    pub analytics_name: Option<String>,
+}
+
+/// The structure of a target type, provided by [`Target::schema`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RouteSchema {
+    /// The variants, in declaration order.
+    pub variants: Vec<VariantSchema>,
+}
+
+/// The structure of a single variant of a target type.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VariantSchema {
+    /// The name of the variant.
+    pub name: String,
+    /// The path segment of the variant, empty for the index target.
+    pub segment: String,
+    /// Additional path segments, matching the variant.
+    pub aliases: Vec<String>,
+    /// If this is the index target.
+    pub index: bool,
+    /// The values, following the segment in the path.
+    pub parameters: Vec<ParameterSchema>,
+    /// The schema of the nested target, if there is one.
+    pub nested: Option<RouteSchema>,
+}
+
+/// A value of a variant, which is part of the path.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParameterSchema {
+    /// The name of the field, or its position for tuple variants.
+    pub name: String,
+    /// The name of the type of the value.
+    pub type_name: String,
+    /// If the segment is optional, using [`MaybeSegment`].
+    pub optional: bool,
+}
+
+/// Context information, provided to [`Target::parse_path_with_context`].
+///
+/// It can hold a value of any type, which is handed over by the router.
+#[derive(Clone, Default)]
+pub struct ParseContext {
+    value: Option<Rc<dyn Any>>,
+}
+
+impl ParseContext {
+    /// Create a new context, holding the provided value.
+    pub fn new<V: 'static>(value: V) -> Self {
+        Self {
+            value: Some(Rc::new(value)),
+        }
+    }
+
+    /// Get the value, if it is present and has the requested type.
+    pub fn get<V: 'static>(&self) -> Option<&V> {
+        self.value.as_ref().and_then(|value| value.downcast_ref())
+    }
+}
+
+impl PartialEq for ParseContext {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.value, &other.value) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Debug for ParseContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParseContext")
+            .field("value", &self.value.as_ref().map(|_| ".."))
+            .finish()
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -96,3 +433,183 @@ where
         Self::new(down, up)
     }
 }
+
+/// An optional, trailing path segment.
+///
+/// When used as the last value of a variant with the `Target` derive, the variant will match with
+/// and without the segment being present. An absent segment doesn't get rendered.
+///
+/// It can also be used in handwritten implementations, using [`MaybeSegment::parse_segment`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MaybeSegment<T> {
+    /// The segment is present, with its parsed value.
+    Present(T),
+    /// The segment is absent.
+    Absent,
+}
+
+impl<T> Default for MaybeSegment<T> {
+    fn default() -> Self {
+        Self::Absent
+    }
+}
+
+impl<T> MaybeSegment<T> {
+    /// Check if the segment is present.
+    pub fn is_present(&self) -> bool {
+        matches!(self, Self::Present(_))
+    }
+
+    /// Get a reference to the value, if the segment is present.
+    pub fn as_option(&self) -> Option<&T> {
+        match self {
+            Self::Present(value) => Some(value),
+            Self::Absent => None,
+        }
+    }
+
+    /// Convert into the value, if the segment is present.
+    pub fn into_option(self) -> Option<T> {
+        self.into()
+    }
+
+    /// Get the value, or the provided default if the segment is absent.
+    pub fn unwrap_or(self, default: T) -> T {
+        self.into_option().unwrap_or(default)
+    }
+}
+
+impl<T> MaybeSegment<T>
+where
+    T: FromStr,
+{
+    /// Try consuming the first segment of the path.
+    ///
+    /// Returns the segment, and the remaining path. If there are no segments left, the segment
+    /// is [`MaybeSegment::Absent`]. If the segment is present, but fails to parse, [`None`] is
+    /// returned.
+    pub fn parse_segment<'a, 'p>(path: &'a [&'p str]) -> Option<(Self, &'a [&'p str])> {
+        match path {
+            [] => Some((Self::Absent, path)),
+            [segment, rest @ ..] => T::from_str(segment)
+                .ok()
+                .map(|value| (Self::Present(value), rest)),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for MaybeSegment<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Self::Present(value),
+            None => Self::Absent,
+        }
+    }
+}
+
+impl<T> From<MaybeSegment<T>> for Option<T> {
+    fn from(value: MaybeSegment<T>) -> Self {
+        match value {
+            MaybeSegment::Present(value) => Some(value),
+            MaybeSegment::Absent => None,
+        }
+    }
+}
+
+impl<T> FromStr for MaybeSegment<T>
+where
+    T: FromStr,
+{
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        T::from_str(s).map(Self::Present)
+    }
+}
+
+impl<T> Display for MaybeSegment<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Present(value) => value.fmt(f),
+            Self::Absent => Ok(()),
+        }
+    }
+}
+
+/// Generate a test, checking that targets survive rendering and parsing their path.
+///
+/// For every target `t` provided, the test asserts that `T::parse_path(&t.render_path())` is
+/// `Some(t)`. By default, the test is named `target_roundtrip`, another name can be provided as
+/// the first argument.
+///
+/// ```
+/// # use yew_nested_router::prelude::*;
+/// # use yew_nested_router::assert_target_roundtrip;
+/// #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// pub enum AppRoute {
+///   #[target(index)]
+///   Index,
+///   User { id: usize },
+/// }
+///
+/// assert_target_roundtrip!(AppRoute, [AppRoute::Index, AppRoute::User { id: 1 }]);
+/// ```
+#[macro_export]
+macro_rules! assert_target_roundtrip {
+    ($t:ty, [$($target:expr),* $(,)?]) => {
+        $crate::assert_target_roundtrip!(target_roundtrip, $t, [$($target),*]);
+    };
+    ($name:ident, $t:ty, [$($target:expr),* $(,)?]) => {
+        #[test]
+        fn $name() {
+            $({
+                let target: $t = $target;
+                let path = $crate::target::Target::render_path(&target);
+                let path = path.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+                assert_eq!(
+                    <$t as $crate::target::Target>::parse_path(&path),
+                    Some(target),
+                    "Target must parse from its own path: {:?}",
+                    path
+                );
+            })*
+        }
+    };
+}
+
+/// Implement [`Default`] for a target, using the provided variant.
+///
+/// The default target can then also be used for the `default` property of the router, using
+/// `Default::default()`. For variants with values, or types referenced by a path, the type and
+/// the default value can be provided separately.
+///
+/// ```
+/// # use yew_nested_router::prelude::*;
+/// # use yew_nested_router::root_target;
+/// #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// pub enum AppRoute {
+///   #[target(index)]
+///   Home,
+///   User { id: usize },
+/// }
+///
+/// root_target!(AppRoute::Home);
+///
+/// assert_eq!(AppRoute::default(), AppRoute::Home);
+/// ```
+#[macro_export]
+macro_rules! root_target {
+    ($t:ident :: $variant:ident) => {
+        $crate::root_target!($t, $t::$variant);
+    };
+    ($t:ty, $default:expr) => {
+        impl ::core::default::Default for $t {
+            fn default() -> Self {
+                $default
+            }
+        }
+    };
+}
@@ -0,0 +1,127 @@
+use crate::history::RouterHistory;
+use crate::router::{Router, RouterMode, RouterProps};
+use crate::target::Target;
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use yew::AttrValue;
+
+thread_local! {
+    static NEXT_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A handle for controlling the history of a [`Router`] in tests.
+///
+/// It is created using [`Router::create_for_testing`], and shares an in-memory history with the
+/// router, using the mode and history key it provides:
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use yew_nested_router::prelude::*;
+/// # #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// # pub enum AppRoute { #[target(index)] Index, Foo }
+/// let handle = Router::<AppRoute>::create_for_testing("/foo");
+///
+/// let app = html!(
+///   <Router<AppRoute> mode={handle.mode()} history_key={handle.key()}>
+///     // content under test
+///   </Router<AppRoute>>
+/// );
+///
+/// assert_eq!(handle.active(), Some(AppRoute::Foo));
+/// handle.push_path("/");
+/// assert_eq!(handle.active(), Some(AppRoute::Index));
+/// ```
+///
+/// The shared history is removed once the handle, and all of its clones, are dropped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouterTestHandle<T: Target> {
+    key: AttrValue,
+    history: RouterHistory,
+    _guard: Rc<HistoryGuard>,
+    _marker: PhantomData<T>,
+}
+
+/// Removes the shared history of a [`RouterTestHandle`] when dropped.
+#[derive(Debug, PartialEq)]
+struct HistoryGuard(AttrValue);
+
+impl Drop for HistoryGuard {
+    fn drop(&mut self) {
+        RouterHistory::remove_shared(&self.0);
+    }
+}
+
+impl<T: Target> RouterTestHandle<T> {
+    fn new(initial_path: &str) -> Self {
+        let id = NEXT_ID.with(|id| id.replace(id.get() + 1));
+        let key = AttrValue::from(format!("__yew_nested_router_testing_{id}"));
+        let history = RouterHistory::shared(&key, &[initial_path.to_string()], None);
+
+        Self {
+            _guard: Rc::new(HistoryGuard(key.clone())),
+            key,
+            history,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The mode for the router.
+    pub fn mode(&self) -> RouterMode {
+        RouterMode::Memory(vec![])
+    }
+
+    /// The history key for the router.
+    pub fn key(&self) -> AttrValue {
+        self.key.clone()
+    }
+
+    /// Replace the current history entry with the provided path.
+    pub fn set_path(&self, path: &str) {
+        self.history.replace(path.to_string());
+    }
+
+    /// Push a new history entry with the provided path.
+    pub fn push_path(&self, path: &str) {
+        self.history.push(path.to_string());
+    }
+
+    /// Go back one history entry, like the "back" button of a browser.
+    pub fn pop_state(&self) {
+        self.history.go(-1);
+    }
+
+    /// The path of the current history entry.
+    pub fn path(&self) -> String {
+        self.history.path()
+    }
+
+    /// The target of the current history entry.
+    pub fn active(&self) -> Option<T> {
+        T::parse_str(&self.history.path())
+    }
+}
+
+impl<T: Target> Router<T> {
+    /// Create a handle for testing components using a router, starting with the provided path.
+    ///
+    /// See [`RouterTestHandle`] for an example.
+    pub fn create_for_testing(initial_path: &str) -> RouterTestHandle<T> {
+        RouterTestHandle::new(initial_path)
+    }
+
+    /// Create the default properties, which can be overridden using the struct update syntax:
+    ///
+    /// ```
+    /// # use yew_nested_router::prelude::*;
+    /// # #[derive(Clone, Debug, PartialEq, Eq, Target)]
+    /// # pub enum AppRoute { #[target(index)] Index, Foo }
+    /// let props = RouterProps {
+    ///     default: Some(AppRoute::Index),
+    ///     ..Router::<AppRoute>::with_default_props()
+    /// };
+    /// ```
+    pub fn with_default_props() -> RouterProps<T> {
+        RouterProps::default()
+    }
+}
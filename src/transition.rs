@@ -0,0 +1,80 @@
+//! Transitions between targets
+
+use crate::scope::ScopeContext;
+use crate::target::Target;
+use std::rc::Rc;
+
+/// An animation, used when transitioning to a new target.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RouteAnimation {
+    SlideLeft,
+    SlideRight,
+    Fade,
+    /// A custom animation, using the provided CSS class name.
+    Custom(String),
+}
+
+impl RouteAnimation {
+    /// The CSS class name of the animation.
+    pub fn class(&self) -> &str {
+        match self {
+            Self::SlideLeft => "slide-left",
+            Self::SlideRight => "slide-right",
+            Self::Fade => "fade",
+            Self::Custom(class) => class,
+        }
+    }
+}
+
+/// A pending transition to a new target, created by
+/// [`RouterContext::go_with_transition`](crate::router::RouterContext::go_with_transition).
+///
+/// Until the transition is committed, the previous target stays active, and the new target is
+/// available as the pending target. The transition is committed by calling [`Self::commit`], or
+/// by dropping the handle.
+#[must_use = "dropping the handle immediately commits the transition"]
+pub struct TransitionHandle<T>
+where
+    T: Target,
+{
+    scope: Rc<ScopeContext<T>>,
+    target: Option<T>,
+}
+
+impl<T> TransitionHandle<T>
+where
+    T: Target,
+{
+    pub(crate) fn new(scope: Rc<ScopeContext<T>>, target: T) -> Self {
+        scope.set_pending(Some(target.clone()));
+        Self {
+            scope,
+            target: Some(target),
+        }
+    }
+
+    /// The target of the transition.
+    pub fn target(&self) -> Option<&T> {
+        self.target.as_ref()
+    }
+
+    /// Commit the transition, making the new target the active one.
+    pub fn commit(mut self) {
+        self.finish();
+    }
+
+    fn finish(&mut self) {
+        if let Some(target) = self.target.take() {
+            self.scope.push(target);
+        }
+    }
+}
+
+impl<T> Drop for TransitionHandle<T>
+where
+    T: Target,
+{
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
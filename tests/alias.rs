@@ -0,0 +1,52 @@
+use yew_nested_router::target::Target;
+use yew_nested_router::Target;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    #[target(alias = "/legacy", alias = "old")]
+    Overview,
+    #[target(rename = "users", alias = "people")]
+    User { id: u32 },
+    #[target(alias = "config")]
+    Settings(Section),
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Section {
+    General,
+}
+
+#[test]
+fn parse_own_path() {
+    assert_eq!(Pages::parse_path(&["overview"]), Some(Pages::Overview));
+    assert_eq!(
+        Pages::parse_path(&["users", "42"]),
+        Some(Pages::User { id: 42 })
+    );
+}
+
+#[test]
+fn parse_alias() {
+    assert_eq!(Pages::parse_path(&["legacy"]), Some(Pages::Overview));
+    assert_eq!(Pages::parse_path(&["old"]), Some(Pages::Overview));
+    assert_eq!(
+        Pages::parse_path(&["people", "42"]),
+        Some(Pages::User { id: 42 })
+    );
+    assert_eq!(
+        Pages::parse_path(&["config", "general"]),
+        Some(Pages::Settings(Section::General))
+    );
+}
+
+#[test]
+fn render_own_path() {
+    assert_eq!(Pages::Overview.render_path(), vec!["overview"]);
+    assert_eq!(Pages::User { id: 42 }.render_path(), vec!["users", "42"]);
+    assert_eq!(
+        Pages::Settings(Section::General).render_path(),
+        vec!["settings", "general"]
+    );
+}
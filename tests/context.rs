@@ -0,0 +1,223 @@
+use tokio::task::LocalSet;
+use yew::prelude::*;
+use yew::LocalServerRenderer;
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Docs(Docs),
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Docs {
+    #[target(index)]
+    Overview,
+    Install,
+}
+
+#[function_component(Ancestors)]
+fn ancestors() -> Html {
+    let router = use_router::<Pages>().expect("Must be nested under a router");
+    html!({ format!("{:?}", router.ancestors()) })
+}
+
+#[function_component(Selected)]
+fn selected() -> Html {
+    let router = use_router::<Pages>().expect("Must be nested under a router");
+    let docs = use_router_selector::<Pages, _, _>(|router| {
+        matches!(router.active(), Some(Pages::Docs(_)))
+    });
+    // the selection must match the target of the same render
+    html!({ format!("docs={docs} active={:?}", router.active()) })
+}
+
+#[function_component(Depth)]
+fn depth() -> Html {
+    let router = use_router::<Pages>().expect("Must be nested under a router");
+    html!({ format!("depth={}", router.active_depth()) })
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct AppProps {
+    path: AttrValue,
+}
+
+#[function_component(App)]
+fn app(props: &AppProps) -> Html {
+    html!(
+        <StaticRouter<Pages> path={props.path.clone()}>
+            <Ancestors/>
+            <Depth/>
+            <Selected/>
+        </StaticRouter<Pages>>
+    )
+}
+
+async fn render(path: &'static str) -> String {
+    let props = AppProps { path: path.into() };
+    LocalSet::new()
+        .run_until(LocalServerRenderer::<App>::with_props(props).render())
+        .await
+}
+
+#[tokio::test]
+async fn ancestors_include_index() {
+    let html = render("/docs/install").await;
+    assert!(html.contains("[Index]"), "{html}");
+}
+
+#[tokio::test]
+async fn index_has_no_ancestors() {
+    let html = render("/").await;
+    assert!(html.contains("[]"), "{html}");
+}
+
+#[tokio::test]
+async fn active_depth() {
+    let html = render("/").await;
+    assert!(html.contains("depth=0"), "{html}");
+
+    let html = render("/docs/install").await;
+    assert!(html.contains("depth=2"), "{html}");
+}
+
+#[tokio::test]
+async fn selector() {
+    let html = render("/").await;
+    assert!(html.contains("docs=false active=Some(Index)"), "{html}");
+
+    let html = render("/docs/install").await;
+    assert!(
+        html.contains("docs=true active=Some(Docs(Install))"),
+        "{html}"
+    );
+}
+
+mod memo {
+    use super::Pages;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use tokio::task::LocalSet;
+    use yew::prelude::*;
+    use yew::suspense::Suspension;
+    use yew::LocalServerRenderer;
+    use yew_nested_router::prelude::*;
+
+    #[derive(Clone, Default)]
+    struct Calls(Rc<Cell<usize>>);
+
+    impl PartialEq for Calls {
+        fn eq(&self, other: &Self) -> bool {
+            Rc::ptr_eq(&self.0, &other.0)
+        }
+    }
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct Props {
+        calls: Calls,
+    }
+
+    #[function_component(Probe)]
+    fn probe(props: &Props) -> HtmlResult {
+        let calls = props.calls.clone();
+        let docs = use_router_selector::<Pages, _, _>(move |router| {
+            calls.0.set(calls.0.get() + 1);
+            matches!(router.active(), Some(Pages::Docs(_)))
+        });
+
+        // suspend once, rendering the component again with the same router
+        let suspended = use_mut_ref(|| false);
+        if !std::mem::replace(&mut *suspended.borrow_mut(), true) {
+            return Err(Suspension::from_future(async {
+                tokio::task::yield_now().await;
+            })
+            .into());
+        }
+
+        Ok(html!({ format!("docs={docs}") }))
+    }
+
+    #[function_component(App)]
+    fn app(props: &Props) -> Html {
+        html!(
+            <StaticRouter<Pages> path="/docs/install">
+                <Suspense>
+                    <Probe calls={props.calls.clone()}/>
+                </Suspense>
+            </StaticRouter<Pages>>
+        )
+    }
+
+    #[tokio::test]
+    async fn selector_is_memoized() {
+        let calls = Calls::default();
+        let props = Props {
+            calls: calls.clone(),
+        };
+        let html = LocalSet::new()
+            .run_until(LocalServerRenderer::<App>::with_props(props).render())
+            .await;
+
+        assert!(html.contains("docs=true"), "{html}");
+        assert_eq!(calls.0.get(), 1);
+    }
+}
+
+mod basename {
+    use tokio::task::LocalSet;
+    use yew::prelude::*;
+    use yew::LocalServerRenderer;
+    use yew_nested_router::prelude::*;
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        Foo,
+    }
+
+    #[function_component(Href)]
+    fn href() -> Html {
+        let router = use_router::<Pages>().expect("Must be nested under a router");
+        let embedded = router.with_basename("/embedded/");
+        html!({
+            format!(
+                "active={:?} href={} embedded={}",
+                router.active(),
+                router.format_path(&Pages::Foo),
+                embedded.format_path(&Pages::Foo)
+            )
+        })
+    }
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct AppProps {
+        handle: RouterTestHandle<Pages>,
+    }
+
+    #[function_component(App)]
+    fn app(props: &AppProps) -> Html {
+        html!(
+            <Router<Pages> mode={props.handle.mode()} history_key={props.handle.key()} base="/app">
+                <Href/>
+            </Router<Pages>>
+        )
+    }
+
+    #[tokio::test]
+    async fn with_basename() {
+        let props = AppProps {
+            handle: Router::<Pages>::create_for_testing("/app/foo"),
+        };
+        let html = LocalSet::new()
+            .run_until(LocalServerRenderer::<App>::with_props(props).render())
+            .await;
+
+        assert!(
+            html.contains("active=Some(Foo) href=/app/foo embedded=/embedded/app/foo"),
+            "{html}"
+        );
+    }
+}
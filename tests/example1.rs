@@ -138,3 +138,12 @@ fn test_default_explicit() {
         })
     );
 }
+
+yew_nested_router::assert_target_roundtrip!(
+    DetailsSection,
+    [
+        DetailsSection::Yaml,
+        DetailsSection::Debug,
+        DetailsSection::Overview
+    ]
+);
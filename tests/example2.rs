@@ -1,3 +1,4 @@
+use yew_nested_router::target::Target;
 use yew_nested_router::Target;
 
 /// test with a nested struct like variant having a default.
@@ -9,3 +10,370 @@ fn test_names() {
         Details { path: String },
     }
 }
+
+#[test]
+fn test_parse_str() {
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        User {
+            id: usize,
+        },
+    }
+
+    assert_eq!(Pages::parse_str("/"), Some(Pages::Index));
+    assert_eq!(Pages::parse_str("/user/42"), Some(Pages::User { id: 42 }));
+    assert_eq!(
+        Pages::parse_str("/user/42?foo=bar#baz"),
+        Some(Pages::User { id: 42 })
+    );
+    assert_eq!(Pages::parse_str("/user/foo"), None);
+}
+
+#[test]
+fn test_path_string_for() {
+    use yew_nested_router::prelude::RouterContext;
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        Page {
+            name: String,
+        },
+    }
+
+    let page = Pages::Page {
+        name: "foo bar".into(),
+    };
+    assert_eq!(RouterContext::path_string_for(&Pages::Index), "/");
+    assert_eq!(RouterContext::path_string_for(&page), "/page/foo%20bar");
+    assert_eq!(
+        Pages::parse_str(&RouterContext::path_string_for(&page)),
+        Some(page)
+    );
+}
+
+#[test]
+fn test_resolve_child() {
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        User {
+            id: usize,
+        },
+    }
+
+    assert_eq!(
+        Pages::resolve_child(&["user", "42", "settings"]),
+        Some((Pages::User { id: 42 }, &["settings"][..]))
+    );
+    assert_eq!(
+        Pages::resolve_child(&["user", "42"]),
+        Some((Pages::User { id: 42 }, &[][..]))
+    );
+    assert_eq!(Pages::resolve_child(&["foo", "bar"]), None);
+}
+
+#[test]
+fn test_from_request() {
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        User {
+            id: usize,
+        },
+    }
+
+    assert_eq!(Pages::from_request("GET", "/"), Some(Pages::Index));
+    assert_eq!(
+        Pages::from_request("POST", "/user/42?foo=bar"),
+        Some(Pages::User { id: 42 })
+    );
+    assert_eq!(Pages::from_request("GET", "/user/foo"), None);
+}
+
+#[test]
+fn test_path_ord() {
+    use yew_nested_router::target::path_ord;
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        User {
+            id: String,
+        },
+        Admin,
+    }
+
+    let mut pages = vec![
+        Pages::User { id: "b".into() },
+        Pages::Admin,
+        Pages::User { id: "a".into() },
+        Pages::Index,
+    ];
+    pages.sort_by(path_ord);
+
+    assert_eq!(
+        pages,
+        vec![
+            Pages::Index,
+            Pages::Admin,
+            Pages::User { id: "a".into() },
+            Pages::User { id: "b".into() },
+        ]
+    );
+}
+
+#[test]
+fn test_captures() {
+    use std::collections::HashMap;
+    use yew_nested_router::target::MaybeSegment;
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        User {
+            id: String,
+            #[target(nested)]
+            details: Details,
+        },
+        Search {
+            query: MaybeSegment<String>,
+        },
+    }
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Details {
+        Overview,
+        Post { post: u32 },
+    }
+
+    assert_eq!(Pages::Index.captures(), HashMap::new());
+    assert_eq!(
+        Pages::User {
+            id: "foo".into(),
+            details: Details::Post { post: 42 },
+        }
+        .captures(),
+        HashMap::from([
+            ("id".to_string(), "foo".to_string()),
+            ("post".to_string(), "42".to_string()),
+        ])
+    );
+    assert_eq!(
+        Pages::User {
+            id: "foo".into(),
+            details: Details::Overview,
+        }
+        .captures(),
+        HashMap::from([("id".to_string(), "foo".to_string())])
+    );
+    assert_eq!(
+        Pages::Search {
+            query: MaybeSegment::Absent
+        }
+        .captures(),
+        HashMap::new()
+    );
+}
+
+#[test]
+fn test_is_index() {
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        User {
+            id: String,
+        },
+    }
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum NoIndex {
+        Admin,
+    }
+
+    assert!(Pages::Index.is_index());
+    assert!(!Pages::User { id: "foo".into() }.is_index());
+    assert!(!NoIndex::Admin.is_index());
+}
+
+#[test]
+fn test_with_captures() {
+    use std::collections::HashMap;
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        User {
+            id: u32,
+            #[target(nested)]
+            details: Details,
+        },
+        Section(Details),
+    }
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Details {
+        Overview,
+        Post { post: u32 },
+    }
+
+    let user = Pages::User {
+        id: 42,
+        details: Details::Post { post: 1 },
+    };
+
+    assert_eq!(
+        user.with_captures(&HashMap::from([("id".to_string(), "43".to_string())])),
+        Some(Pages::User {
+            id: 43,
+            details: Details::Post { post: 1 },
+        })
+    );
+    assert_eq!(
+        user.with_captures(&HashMap::from([("post".to_string(), "2".to_string())])),
+        Some(Pages::User {
+            id: 42,
+            details: Details::Post { post: 2 },
+        })
+    );
+    assert_eq!(
+        user.with_captures(&HashMap::from([("id".to_string(), "foo".to_string())])),
+        None
+    );
+    assert_eq!(
+        Pages::Section(Details::Post { post: 1 })
+            .with_captures(&HashMap::from([("post".to_string(), "2".to_string())])),
+        Some(Pages::Section(Details::Post { post: 2 }))
+    );
+}
+
+#[test]
+fn test_children() {
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        User {
+            id: String,
+        },
+        Section(Details),
+        Admin {
+            #[target(nested)]
+            details: Details,
+        },
+    }
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Details {
+        Overview,
+        Post { post: u32 },
+        Settings,
+    }
+
+    assert_eq!(
+        Pages::children(),
+        vec![
+            Pages::Index,
+            Pages::Section(Details::Overview),
+            Pages::Section(Details::Settings),
+            Pages::Admin {
+                details: Details::Overview
+            },
+            Pages::Admin {
+                details: Details::Settings
+            },
+        ]
+    );
+}
+
+mod root {
+    use yew_nested_router::{root_target, Target};
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        User {
+            id: usize,
+        },
+    }
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Users {
+        User { id: usize },
+    }
+
+    root_target!(Pages::Index);
+    root_target!(Users, Users::User { id: 0 });
+
+    #[test]
+    fn test_root_target() {
+        assert_eq!(Pages::default(), Pages::Index);
+        assert_eq!(Users::default(), Users::User { id: 0 });
+    }
+}
+
+#[test]
+fn test_subroutes() {
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(subroutes)]
+        Docs,
+        About,
+    }
+
+    assert_eq!(Pages::parse_path(&["docs"]), Some(Pages::Docs));
+    assert_eq!(
+        Pages::parse_path(&["docs", "intro", "setup"]),
+        Some(Pages::Docs)
+    );
+    assert_eq!(Pages::parse_path(&["about", "more"]), None);
+    assert!(Pages::Docs.accepts_subroute(&["intro"]));
+    assert!(!Pages::About.accepts_subroute(&["more"]));
+}
+
+#[test]
+fn test_subroutes_predicate() {
+    fn is_chapter(remaining: &[&str]) -> bool {
+        matches!(remaining, ["intro" | "setup"])
+    }
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(subroutes = "is_chapter")]
+        Docs,
+        About,
+    }
+
+    assert_eq!(Pages::parse_path(&["docs"]), Some(Pages::Docs));
+    assert_eq!(Pages::parse_path(&["docs", "setup"]), Some(Pages::Docs));
+    assert_eq!(Pages::parse_path(&["docs", "other"]), None);
+    assert_eq!(Pages::parse_path(&["docs", "intro", "setup"]), None);
+    assert!(Pages::Docs.accepts_subroute(&["intro"]));
+    assert!(!Pages::Docs.accepts_subroute(&["intro", "setup"]));
+    assert!(!Pages::About.accepts_subroute(&["intro"]));
+}
+
+#[test]
+fn test_display_name() {
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        User {
+            id: usize,
+        },
+    }
+
+    assert_eq!(Pages::Index.display_name(), "");
+    assert_eq!(Pages::User { id: 42 }.display_name(), "42");
+}
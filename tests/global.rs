@@ -0,0 +1,40 @@
+use tokio::task::LocalSet;
+use yew::prelude::*;
+use yew::LocalServerRenderer;
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Foo,
+    Bar,
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct NestedProps {
+    outer: RouterTestHandle<Pages>,
+    inner: RouterTestHandle<Pages>,
+}
+
+#[function_component(Nested)]
+fn nested(props: &NestedProps) -> Html {
+    html!(
+        <Router<Pages> mode={props.outer.mode()} history_key={props.outer.key()}>
+            <Router<Pages> mode={props.inner.mode()} history_key={props.inner.key()}>
+            </Router<Pages>>
+        </Router<Pages>>
+    )
+}
+
+#[tokio::test]
+#[should_panic(expected = "Only one router of the same target type may exist at a time")]
+async fn two_routers_of_the_same_type() {
+    let props = NestedProps {
+        outer: Router::<Pages>::create_for_testing("/foo"),
+        inner: Router::<Pages>::create_for_testing("/bar"),
+    };
+    LocalSet::new()
+        .run_until(LocalServerRenderer::<Nested>::with_props(props).render())
+        .await;
+}
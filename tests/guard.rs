@@ -0,0 +1,78 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Admin,
+    Profile,
+}
+
+#[test]
+fn test_no_guards() {
+    let guards = RouterGuardContext::<Pages>::new();
+    assert!(guards.allows(&Pages::Admin));
+}
+
+#[test]
+fn test_all_guards() {
+    let guards = RouterGuardContext::<Pages>::new()
+        .with_guard(|target| target != Pages::Admin)
+        .with_guard(|target| target != Pages::Profile);
+
+    assert!(guards.allows(&Pages::Index));
+    assert!(!guards.allows(&Pages::Admin));
+    assert!(!guards.allows(&Pages::Profile));
+}
+
+mod refused {
+    use super::Pages;
+    use tokio::task::LocalSet;
+    use yew::prelude::*;
+    use yew::LocalServerRenderer;
+    use yew_nested_router::prelude::*;
+
+    #[function_component(Probe)]
+    fn probe() -> Html {
+        let router = use_router::<Pages>().expect("Must be nested under a router");
+        let started = use_mut_ref(|| false);
+        if !std::mem::replace(&mut *started.borrow_mut(), true) {
+            router.go_with_transition(Pages::Admin).commit();
+            router.go_with_title(Pages::Admin, "Admin");
+        }
+        html!(<span>{ format!("{:?}", router.active()) }</span>)
+    }
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct AppProps {
+        handle: RouterTestHandle<Pages>,
+    }
+
+    #[function_component(App)]
+    fn app(props: &AppProps) -> Html {
+        let guards = RouterGuardContext::<Pages>::new().with_guard(|target| target != Pages::Admin);
+        html!(
+            <ContextProvider<RouterGuardContext<Pages>> context={guards}>
+                <Router<Pages> mode={props.handle.mode()} history_key={props.handle.key()}>
+                    <Probe/>
+                </Router<Pages>>
+            </ContextProvider<RouterGuardContext<Pages>>>
+        )
+    }
+
+    #[tokio::test]
+    async fn test_refusing_guard() {
+        let handle = Router::<Pages>::create_for_testing("/");
+
+        let props = AppProps {
+            handle: handle.clone(),
+        };
+        let html = LocalSet::new()
+            .run_until(LocalServerRenderer::<App>::with_props(props).render())
+            .await;
+
+        assert!(html.contains("Some(Index)"), "{html}");
+        assert_eq!(handle.path(), "/");
+        assert_eq!(handle.active(), Some(Pages::Index));
+    }
+}
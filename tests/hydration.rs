@@ -0,0 +1,36 @@
+#![cfg(feature = "hydration")]
+
+use serde::{Deserialize, Serialize};
+use yew_nested_router::hydration::{deserialize_initial_state, serialize_initial_state};
+use yew_nested_router::Target;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    User {
+        id: String,
+    },
+}
+
+#[test]
+fn roundtrip() {
+    let target = Pages::User { id: "42".into() };
+    let json = serialize_initial_state(&target);
+    assert_eq!(deserialize_initial_state::<Pages>(&json), Some(target));
+}
+
+#[test]
+fn escape_script() {
+    let target = Pages::User {
+        id: "</script>".into(),
+    };
+    let json = serialize_initial_state(&target);
+    assert!(!json.contains("</script>"));
+    assert_eq!(deserialize_initial_state::<Pages>(&json), Some(target));
+}
+
+#[test]
+fn invalid() {
+    assert_eq!(deserialize_initial_state::<Pages>("{"), None);
+}
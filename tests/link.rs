@@ -0,0 +1,102 @@
+use tokio::task::LocalSet;
+use yew::prelude::*;
+use yew::LocalServerRenderer;
+use yew_nested_router::components::{ExternalLink, Link};
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Foo,
+}
+
+#[function_component(App)]
+fn app() -> Html {
+    html!(
+        <StaticRouter<Pages> path="/">
+            <Link<Pages> target={Pages::Foo}>{ "Foo" }</Link<Pages>>
+            <ExternalLink href="https://example.com" class="external">{ "Example" }</ExternalLink>
+        </StaticRouter<Pages>>
+    )
+}
+
+#[tokio::test]
+async fn links() {
+    let html = LocalSet::new()
+        .run_until(LocalServerRenderer::<App>::new().render())
+        .await;
+
+    assert!(html.contains(r#"href="/foo""#), "{html}");
+    assert!(
+        html.contains(r#"<a href="https://example.com" class="external">Example</a>"#),
+        "{html}"
+    );
+}
+
+mod link_props {
+    use tokio::task::LocalSet;
+    use yew::prelude::*;
+    use yew::LocalServerRenderer;
+    use yew_nested_router::prelude::*;
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(index)]
+        Index,
+        Foo,
+        #[target(rename = "foobar")]
+        FooBar,
+    }
+
+    #[function_component(Links)]
+    fn links() -> Html {
+        let router = use_router::<Pages>().expect("Must be nested under a router");
+        let targets = [Pages::Index, Pages::Foo, Pages::FooBar];
+        html!({
+            targets
+                .iter()
+                .map(|target| {
+                    let props = router.get_link_props(target);
+                    format!("{target:?}={}/{};", props.is_active, props.is_exact)
+                })
+                .collect::<String>()
+        })
+    }
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct AppProps {
+        path: AttrValue,
+    }
+
+    #[function_component(App)]
+    fn app(props: &AppProps) -> Html {
+        html!(
+            <StaticRouter<Pages> path={props.path.clone()}>
+                <Links/>
+            </StaticRouter<Pages>>
+        )
+    }
+
+    async fn render(path: &'static str) -> String {
+        let props = AppProps { path: path.into() };
+        LocalSet::new()
+            .run_until(LocalServerRenderer::<App>::with_props(props).render())
+            .await
+    }
+
+    #[tokio::test]
+    async fn active_by_segment() {
+        let html = render("/foobar").await;
+        assert!(
+            html.contains("Index=false/false;Foo=false/false;FooBar=true/true;"),
+            "{html}"
+        );
+
+        let html = render("/").await;
+        assert!(
+            html.contains("Index=true/true;Foo=false/false;FooBar=false/false;"),
+            "{html}"
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use yew_nested_router::target::Target;
+use yew_nested_router::Target;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    #[target(load_hint = 500)]
+    Dashboard,
+    #[target(load_hint = 50)]
+    User { id: u32 },
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Plain {
+    Index,
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Heavy {
+    #[target(load_hint = 100)]
+    Index,
+}
+
+#[test]
+fn test_load_hint() {
+    assert_eq!(Pages::Index.estimated_load_time_ms(), 0);
+    assert_eq!(Pages::Dashboard.estimated_load_time_ms(), 500);
+    assert_eq!(Pages::User { id: 1 }.estimated_load_time_ms(), 50);
+}
+
+#[test]
+fn test_without_load_hint() {
+    assert_eq!(Plain::Index.estimated_load_time_ms(), 0);
+}
+
+#[test]
+fn test_all_load_hints() {
+    assert_eq!(Heavy::Index.estimated_load_time_ms(), 100);
+}
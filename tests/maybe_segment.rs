@@ -0,0 +1,63 @@
+use yew_nested_router::target::{MaybeSegment, Target};
+use yew_nested_router::Target;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    Report { page: MaybeSegment<usize> },
+    Chart(String, MaybeSegment<usize>),
+}
+
+#[test]
+fn test_absent() {
+    assert_eq!(
+        Pages::parse_path(&["report"]),
+        Some(Pages::Report {
+            page: MaybeSegment::Absent
+        })
+    );
+    assert_eq!(
+        Pages::parse_path(&["chart", "foo"]),
+        Some(Pages::Chart("foo".into(), MaybeSegment::Absent))
+    );
+
+    assert_eq!(
+        Pages::Report {
+            page: MaybeSegment::Absent
+        }
+        .render_path(),
+        vec!["report".to_string()]
+    );
+}
+
+#[test]
+fn test_present() {
+    assert_eq!(
+        Pages::parse_path(&["report", "2"]),
+        Some(Pages::Report {
+            page: MaybeSegment::Present(2)
+        })
+    );
+    assert_eq!(
+        Pages::parse_path(&["chart", "foo", "3"]),
+        Some(Pages::Chart("foo".into(), MaybeSegment::Present(3)))
+    );
+    assert_eq!(Pages::parse_path(&["report", "two"]), None);
+
+    assert_eq!(
+        Pages::Chart("foo".into(), MaybeSegment::Present(3)).render_path(),
+        vec!["chart".to_string(), "foo".to_string(), "3".to_string()]
+    );
+}
+
+#[test]
+fn test_parse_segment() {
+    assert_eq!(
+        MaybeSegment::<usize>::parse_segment(&[]),
+        Some((MaybeSegment::Absent, &[][..]))
+    );
+    assert_eq!(
+        MaybeSegment::<usize>::parse_segment(&["1", "rest"]),
+        Some((MaybeSegment::Present(1), &["rest"][..]))
+    );
+    assert_eq!(MaybeSegment::<usize>::parse_segment(&["one"]), None);
+}
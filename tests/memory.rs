@@ -0,0 +1,36 @@
+use tokio::task::LocalSet;
+use yew::prelude::*;
+use yew::LocalServerRenderer;
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Foo,
+}
+
+#[function_component(Active)]
+fn active() -> Html {
+    let router = use_router::<Pages>().expect("Must be nested under a router");
+    html!({ format!("{:?}", router.active()) })
+}
+
+#[function_component(App)]
+fn app() -> Html {
+    html!(
+        <Router<Pages> mode={RouterMode::Memory(vec!["/foo".into()])}>
+            <Active/>
+        </Router<Pages>>
+    )
+}
+
+// in debug builds, this must not try to persist the target to the storage of the browser
+#[tokio::test]
+async fn without_history_key() {
+    let html = LocalSet::new()
+        .run_until(LocalServerRenderer::<App>::new().render())
+        .await;
+
+    assert!(html.contains("Some(Foo)"), "{html}");
+}
@@ -0,0 +1,77 @@
+use yew_nested_router::target::Target;
+use yew_nested_router::Target;
+
+#[test]
+fn test_priority() {
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        Page {
+            name: String,
+        },
+        #[target(rename = "page", priority = 1)]
+        About(#[target(value)] String),
+    }
+
+    // without the priority, "Page" would be tried first
+    assert_eq!(
+        Pages::parse_path(&["page", "foo"]),
+        Some(Pages::About("foo".into()))
+    );
+}
+
+#[test]
+fn test_negative_priority() {
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(priority = 1)]
+        Page { name: String },
+        #[target(rename = "page", priority = -1)]
+        About(#[target(value)] String),
+    }
+
+    assert_eq!(
+        Pages::parse_path(&["page", "foo"]),
+        Some(Pages::Page { name: "foo".into() })
+    );
+}
+
+// the derive warns about the conflicting variants, using a deprecation
+#[test]
+#[allow(deprecated)]
+fn test_declaration_order() {
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        Page {
+            name: String,
+        },
+        #[target(rename = "page")]
+        About(#[target(value)] String),
+    }
+
+    assert_eq!(
+        Pages::parse_path(&["page", "foo"]),
+        Some(Pages::Page { name: "foo".into() })
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_declaration_order_alias() {
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(alias = "page")]
+        About(#[target(value)] String),
+        Page {
+            name: String,
+        },
+    }
+
+    assert_eq!(
+        Pages::parse_path(&["page", "foo"]),
+        Some(Pages::About("foo".into()))
+    );
+    assert_eq!(
+        Pages::parse_path(&["about", "foo"]),
+        Some(Pages::About("foo".into()))
+    );
+}
@@ -0,0 +1,69 @@
+use yew_nested_router::target::{
+    MaybeSegment, ParameterSchema, RouteSchema, Target, VariantSchema,
+};
+use yew_nested_router::Target;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    #[target(alias = "people")]
+    User {
+        id: u32,
+        tab: MaybeSegment<String>,
+    },
+    Settings(Section),
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Section {
+    General,
+}
+
+#[test]
+fn test_schema() {
+    let section = RouteSchema {
+        variants: vec![VariantSchema {
+            name: "General".into(),
+            segment: "general".into(),
+            ..Default::default()
+        }],
+    };
+
+    assert_eq!(
+        Pages::schema(),
+        RouteSchema {
+            variants: vec![
+                VariantSchema {
+                    name: "Index".into(),
+                    index: true,
+                    ..Default::default()
+                },
+                VariantSchema {
+                    name: "User".into(),
+                    segment: "user".into(),
+                    aliases: vec!["people".into()],
+                    parameters: vec![
+                        ParameterSchema {
+                            name: "id".into(),
+                            type_name: "u32".into(),
+                            optional: false,
+                        },
+                        ParameterSchema {
+                            name: "tab".into(),
+                            type_name: std::any::type_name::<MaybeSegment<String>>().into(),
+                            optional: true,
+                        },
+                    ],
+                    ..Default::default()
+                },
+                VariantSchema {
+                    name: "Settings".into(),
+                    segment: "settings".into(),
+                    nested: Some(section),
+                    ..Default::default()
+                },
+            ],
+        }
+    );
+}
@@ -0,0 +1,47 @@
+use tokio::task::LocalSet;
+use yew::prelude::*;
+use yew::LocalServerRenderer;
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Foo,
+}
+
+#[function_component(Snapshots)]
+fn snapshots() -> Html {
+    let snapshots = use_router_snapshots::<Pages>().expect("Must be nested under a router");
+    let targets = snapshots
+        .iter()
+        .map(|snapshot| snapshot.active_target.clone())
+        .collect::<Vec<_>>();
+    html!({ format!("{targets:?}") })
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct AppProps {
+    handle: RouterTestHandle<Pages>,
+}
+
+#[function_component(App)]
+fn app(props: &AppProps) -> Html {
+    html!(
+        <Router<Pages> mode={props.handle.mode()} history_key={props.handle.key()}>
+            <Snapshots/>
+        </Router<Pages>>
+    )
+}
+
+#[tokio::test]
+async fn records_initial_snapshot() {
+    let handle = Router::<Pages>::create_for_testing("/foo");
+
+    let props = AppProps { handle };
+    let html = LocalSet::new()
+        .run_until(LocalServerRenderer::<App>::with_props(props).render())
+        .await;
+
+    assert!(html.contains("[Some(Foo)]"), "{html}");
+}
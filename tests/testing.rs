@@ -0,0 +1,107 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Foo,
+    Bar,
+}
+
+#[test]
+fn initial_path() {
+    let handle = Router::<Pages>::create_for_testing("/foo");
+    assert_eq!(handle.path(), "/foo");
+    assert_eq!(handle.active(), Some(Pages::Foo));
+}
+
+#[test]
+fn push_and_pop() {
+    let handle = Router::<Pages>::create_for_testing("/");
+    handle.push_path("/foo");
+    handle.push_path("/bar");
+    assert_eq!(handle.active(), Some(Pages::Bar));
+
+    handle.pop_state();
+    assert_eq!(handle.active(), Some(Pages::Foo));
+
+    handle.set_path("/");
+    assert_eq!(handle.active(), Some(Pages::Index));
+
+    handle.pop_state();
+    assert_eq!(handle.active(), Some(Pages::Index));
+}
+
+#[test]
+fn independent_handles() {
+    let first = Router::<Pages>::create_for_testing("/foo");
+    let second = Router::<Pages>::create_for_testing("/bar");
+    assert_ne!(first.key(), second.key());
+
+    first.push_path("/");
+    assert_eq!(second.active(), Some(Pages::Bar));
+}
+
+#[test]
+fn default_props() {
+    let props = RouterProps {
+        default: Some(Pages::Index),
+        ..Router::<Pages>::with_default_props()
+    };
+    assert_eq!(props.default, Some(Pages::Index));
+    assert!(props.anchor_scroll);
+    assert_eq!(props.mode, RouterMode::Browser);
+}
+
+#[test]
+fn default_props_match_macro() {
+    // `props!` uses the same builder as `html! { <Router<Pages>/> }` does
+    let props = yew::props!(RouterProps::<Pages> {});
+    assert_eq!(Router::<Pages>::with_default_props(), props);
+    assert_eq!(RouterProps::<Pages>::default(), props);
+}
+
+mod cleanup {
+    use super::Pages;
+    use tokio::task::LocalSet;
+    use yew::prelude::*;
+    use yew::LocalServerRenderer;
+    use yew_nested_router::prelude::*;
+
+    #[function_component(Active)]
+    fn active() -> Html {
+        let router = use_router::<Pages>().expect("Must be nested under a router");
+        html!({ format!("{:?}", router.active()) })
+    }
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct AppProps {
+        key: AttrValue,
+    }
+
+    #[function_component(App)]
+    fn app(props: &AppProps) -> Html {
+        html!(
+            <Router<Pages> mode={RouterMode::Memory(vec!["/bar".into()])} history_key={props.key.clone()}>
+                <Active/>
+            </Router<Pages>>
+        )
+    }
+
+    #[tokio::test]
+    async fn drop_removes_history() {
+        let handle = Router::<Pages>::create_for_testing("/foo");
+        let key = handle.key();
+        let clone = handle.clone();
+        drop(handle);
+        assert_eq!(clone.active(), Some(Pages::Foo));
+        drop(clone);
+
+        let props = AppProps { key };
+        let html = LocalSet::new()
+            .run_until(LocalServerRenderer::<App>::with_props(props).render())
+            .await;
+
+        assert!(html.contains("Some(Bar)"), "{html}");
+    }
+}
@@ -7,7 +7,7 @@ use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote, quote_spanned};
 use syn::{
     parse_macro_input, punctuated::Punctuated, spanned::Spanned, Data, DataEnum, DeriveInput,
-    Field, Fields, Path, Token, Variant,
+    Field, Fields, Path, Token, Type, Variant,
 };
 
 /// Get the value of the path segment
@@ -26,6 +26,11 @@ fn to_discriminator(variant: &Variant, opts: &Opts) -> String {
 struct Opts {
     index: Flag,
     rename: Option<String>,
+    priority: i32,
+    #[darling(multiple)]
+    alias: Vec<String>,
+    load_hint: Option<u32>,
+    subroutes: Option<Override<String>>,
 }
 
 #[derive(FromField, Default)]
@@ -45,6 +50,19 @@ impl FieldOpts {
     }
 }
 
+/// Check if the field is an optional segment (`MaybeSegment<T>`).
+fn is_maybe_segment(field: &Field) -> bool {
+    match &field.ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "MaybeSegment")
+            .unwrap_or_default(),
+        _ => false,
+    }
+}
+
 /// Find the fields which points to the nested target.
 fn nested_field<P>(
     expect_target: bool,
@@ -60,20 +78,26 @@ fn nested_field<P>(
         let last = i == fields.len() - 1;
 
         if last {
-            if (expect_target || opts.nested.is_present()) && !opts.value.is_present() {
+            if (expect_target || opts.nested.is_present())
+                && !opts.value.is_present()
+                && !is_maybe_segment(field)
+            {
                 // this is the last field, and it is flagged as nested, we can return now
                 return (values, Some(field));
             }
         } else {
+            let name = || {
+                field
+                    .ident
+                    .as_ref()
+                    .map(|i| i.to_string())
+                    .unwrap_or_else(|| format!("{}", i))
+            };
             if opts.nested.is_present() {
-                panic!(
-                    "Only the last field can be a nested target: {}",
-                    field
-                        .ident
-                        .as_ref()
-                        .map(|i| i.to_string())
-                        .unwrap_or_else(|| format!("{}", i))
-                );
+                panic!("Only the last field can be a nested target: {}", name());
+            }
+            if is_maybe_segment(field) {
+                panic!("Only the last field can be an optional segment: {}", name());
             }
         }
 
@@ -156,7 +180,7 @@ fn capture_values<'f, P, F>(
     impl Iterator<Item = TokenStream> + 'f,
 )
 where
-    F: Fn(&Ident) -> TokenStream + 'static,
+    F: Fn(&Field, &Ident) -> TokenStream + 'static,
 {
     let (values, nested) = nested_field(expect_target, fields);
 
@@ -174,12 +198,24 @@ where
     let values = values.into_iter().enumerate().map(move |(i, f)| {
         let anon = format_ident!("arg_{}", i);
         let name = f.ident.as_ref().unwrap_or(&anon);
-        push(name)
+        push(f, name)
     });
 
     (captures, values)
 }
 
+/// render a single value into the path.
+fn render_value(field: &Field, name: &Ident) -> TokenStream {
+    match is_maybe_segment(field) {
+        true => quote! {
+            if let yew_nested_router::target::MaybeSegment::Present(#name) = #name {
+                __internal_path.push(#name.to_string());
+            }
+        },
+        false => quote! { __internal_path.push(#name.to_string()); },
+    }
+}
+
 /// rendering (local) target to its path.
 fn render_self(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
     data.variants.iter().map(|v| {
@@ -198,9 +234,7 @@ fn render_self(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
             // nested route
             Fields::Unnamed(fields) => {
                 let (captures, values) =
-                    capture_values(true, &fields.unnamed, quote! { _ }, |name| {
-                        quote! { __internal_path.push(#name.to_string()); }
-                    });
+                    capture_values(true, &fields.unnamed, quote! { _ }, render_value);
 
                 quote_spanned! { v.span() =>
                     Self::#name(#(#captures),*) => {
@@ -212,9 +246,7 @@ fn render_self(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
             // variables
             Fields::Named(fields) => {
                 let (captures, values) =
-                    capture_values(false, &fields.named, quote! { .. }, |name| {
-                        quote! { __internal_path.push(#name.to_string()); }
-                    });
+                    capture_values(false, &fields.named, quote! { .. }, render_value);
 
                 quote_spanned! { v.span() =>
                     Self::#name { #(#captures),* } => {
@@ -227,49 +259,465 @@ fn render_self(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
     })
 }
 
-/// parsing the path, into a target
-fn parse_path(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
+/// collect the named values, this needs to dive into nested entries.
+fn captures(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
     data.variants.iter().map(|v| {
         let name = &v.ident;
 
-        let opts = Opts::from_variant(v).expect("Unable to parse options");
-        let value = to_discriminator(&v, &opts);
+        match &v.fields {
+            Fields::Unit => {
+                quote_spanned! { v.span() =>
+                    Self::#name => {}
+                }
+            }
+            Fields::Unnamed(fields) => {
+                // only the nested target can provide named values
+                let (values, nested) = nested_field(true, &fields.unnamed);
+
+                let values = values
+                    .iter()
+                    .map(|_| quote!(_))
+                    .chain(nested.map(|_| quote!(nested)));
+
+                let nested = match nested.is_some() {
+                    true => quote! { nested.captures_into(__internal_captures); },
+                    false => quote! {},
+                };
+
+                quote_spanned! { v.span() =>
+                    Self::#name(#(#values),*) => {
+                        #nested
+                    }
+                }
+            }
+            Fields::Named(fields) => {
+                let (values, nested) = nested_field(false, &fields.named);
+
+                let captures = values
+                    .iter()
+                    .map(|f| {
+                        let name = f.ident.as_ref().expect("Field must have a name");
+                        quote!(#name)
+                    })
+                    .chain(nested.map(|nested| {
+                        let nested = nested.ident.as_ref().expect("Field must have a name");
+                        quote!(#nested: __internal_nested)
+                    }));
+
+                let inserts = values.iter().map(|f| {
+                    let name = f.ident.as_ref().expect("Field must have a name");
+                    let key = name.to_string();
+                    match is_maybe_segment(f) {
+                        true => quote! {
+                            if let yew_nested_router::target::MaybeSegment::Present(#name) = #name {
+                                __internal_captures.insert(#key.into(), #name.to_string());
+                            }
+                        },
+                        false => quote! {
+                            __internal_captures.insert(#key.into(), #name.to_string());
+                        },
+                    }
+                });
+
+                let nested = match nested.is_some() {
+                    true => quote! { __internal_nested.captures_into(__internal_captures); },
+                    false => quote! {},
+                };
+
+                quote_spanned! { v.span() =>
+                    Self::#name{ #(#captures,)* .. } => {
+                        #(#inserts)*
+                        #nested
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// create a copy with replaced named values, this needs to dive into nested entries.
+fn with_captures(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
+    data.variants.iter().map(|v| {
+        let name = &v.ident;
 
         match &v.fields {
             Fields::Unit => {
                 quote_spanned! { v.span() =>
-                    [#value] => Some(Self::#name)
+                    Self::#name => Some(Self::#name)
+                }
+            }
+            Fields::Unnamed(fields) => {
+                let (values, nested) = nested_field(true, &fields.unnamed);
+
+                let captures = values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, _)| {
+                        let cap = format_ident!("arg_{i}");
+                        quote!(#cap)
+                    })
+                    .chain(nested.map(|_| quote!(nested)))
+                    .collect::<Vec<_>>();
+
+                let values = values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, _)| {
+                        let cap = format_ident!("arg_{i}");
+                        quote!(#cap.clone())
+                    })
+                    .chain(nested.map(|_| quote!(nested.with_captures(__internal_captures)?)));
+
+                quote_spanned! { v.span() =>
+                    Self::#name(#(#captures),*) => Some(Self::#name(#(#values),*))
+                }
+            }
+            Fields::Named(fields) => {
+                let (values, nested) = nested_field(false, &fields.named);
+
+                let captures = values
+                    .iter()
+                    .chain(nested.iter())
+                    .map(|f| {
+                        let name = f.ident.as_ref().expect("Field must have a name");
+                        quote!(#name)
+                    })
+                    .collect::<Vec<_>>();
+
+                let values = values
+                    .iter()
+                    .map(|f| {
+                        let name = f.ident.as_ref().expect("Field must have a name");
+                        let key = name.to_string();
+                        quote! {
+                            #name: match __internal_captures.get(#key) {
+                                Some(value) => std::str::FromStr::from_str(value).ok()?,
+                                None => #name.clone(),
+                            }
+                        }
+                    })
+                    .chain(nested.map(|f| {
+                        let name = f.ident.as_ref().expect("Field must have a name");
+                        quote!(#name: #name.with_captures(__internal_captures)?)
+                    }));
+
+                quote_spanned! { v.span() =>
+                    Self::#name{ #(#captures),* } => Some(Self::#name { #(#values),* })
+                }
+            }
+        }
+    })
+}
+
+/// enumerate all targets which don't carry values, this needs to dive into nested entries.
+fn children(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
+    data.variants.iter().filter_map(|v| {
+        let name = &v.ident;
+
+        match &v.fields {
+            Fields::Unit => Some(quote_spanned! { v.span() =>
+                __internal_children.push(Self::#name);
+            }),
+            Fields::Unnamed(fields) => match nested_field(true, &fields.unnamed) {
+                (values, Some(nested)) if values.is_empty() => {
+                    let t = &nested.ty;
+                    Some(quote_spanned! { v.span() =>
+                        __internal_children.extend(
+                            <#t as yew_nested_router::target::Target>::children()
+                                .into_iter()
+                                .map(Self::#name)
+                        );
+                    })
+                }
+                _ => None,
+            },
+            Fields::Named(fields) => match nested_field(false, &fields.named) {
+                (values, Some(nested)) if values.is_empty() => {
+                    let t = &nested.ty;
+                    let nested = nested.ident.as_ref().expect("Field must have a name");
+                    Some(quote_spanned! { v.span() =>
+                        __internal_children.extend(
+                            <#t as yew_nested_router::target::Target>::children()
+                                .into_iter()
+                                .map(|#nested| Self::#name { #nested })
+                        );
+                    })
+                }
+                _ => None,
+            },
+        }
+    })
+}
+
+/// check if the target is an index target, flagged using `#[target(index)]`.
+fn is_index(data: &DataEnum) -> TokenStream {
+    let variants = data
+        .variants
+        .iter()
+        .filter(|v| {
+            Opts::from_variant(v)
+                .expect("Unable to parse options")
+                .index
+                .is_present()
+        })
+        .map(|v| {
+            let name = &v.ident;
+            match &v.fields {
+                Fields::Unit => quote_spanned! { v.span() => Self::#name },
+                Fields::Unnamed(_) => quote_spanned! { v.span() => Self::#name(..) },
+                Fields::Named(_) => quote_spanned! { v.span() => Self::#name{..} },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    match variants.is_empty() {
+        true => quote! { false },
+        false => quote! { matches!(self, #(#variants)|*) },
+    }
+}
+
+/// check if the target accepts additional segments, flagged using `#[target(subroutes)]`, or
+/// checked by the function of `#[target(subroutes = "path::to_fn")]`.
+fn accepts_subroute(data: &DataEnum) -> TokenStream {
+    let variants = data
+        .variants
+        .iter()
+        .filter_map(|v| {
+            let subroutes = Opts::from_variant(v)
+                .expect("Unable to parse options")
+                .subroutes?;
+            let name = &v.ident;
+            if !matches!(v.fields, Fields::Unit) {
+                panic!(
+                    "Only unit variants can accept subroutes (on variant '{}')",
+                    v.ident
+                );
+            }
+            Some(match subroutes {
+                Override::Inherit => quote_spanned! { v.span() => Self::#name => true },
+                Override::Explicit(predicate) => {
+                    let predicate = syn::parse_str::<Path>(&predicate).expect("Path to function");
+                    quote_spanned! { v.span() => Self::#name => #predicate(__internal_remaining) }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    match (variants.is_empty(), variants.len() == data.variants.len()) {
+        (true, _) => quote! { false },
+        (false, true) => quote! { match self { #(#variants ,)* } },
+        (false, false) => quote! { match self { #(#variants ,)* _ => false } },
+    }
+}
+
+/// the estimated load time of the target, set using `#[target(load_hint = N)]`.
+fn estimated_load_time_ms(data: &DataEnum) -> TokenStream {
+    let variants = data
+        .variants
+        .iter()
+        .filter_map(|v| {
+            let hint = Opts::from_variant(v)
+                .expect("Unable to parse options")
+                .load_hint?;
+            let name = &v.ident;
+            Some(match &v.fields {
+                Fields::Unit => quote_spanned! { v.span() => Self::#name => #hint },
+                Fields::Unnamed(_) => quote_spanned! { v.span() => Self::#name(..) => #hint },
+                Fields::Named(_) => quote_spanned! { v.span() => Self::#name{..} => #hint },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    match (variants.is_empty(), variants.len() == data.variants.len()) {
+        (true, _) => quote! { 0 },
+        (false, true) => quote! { match self { #(#variants ,)* } },
+        (false, false) => quote! { match self { #(#variants ,)* _ => 0 } },
+    }
+}
+
+/// describe the variants, for runtime introspection.
+fn schema(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
+    data.variants.iter().map(|v| {
+        let opts = Opts::from_variant(v).expect("Unable to parse options");
+        let mut discriminators = discriminators(v, &opts).into_iter();
+        let segment = discriminators.next().unwrap_or_default();
+        let aliases = discriminators;
+        let name = v.ident.to_string();
+        let index = opts.index.is_present();
+
+        let (values, nested) = match &v.fields {
+            Fields::Unit => (vec![], None),
+            Fields::Unnamed(fields) => nested_field(true, &fields.unnamed),
+            Fields::Named(fields) => nested_field(false, &fields.named),
+        };
+
+        let parameters = values.iter().enumerate().map(|(i, f)| {
+            let name = f
+                .ident
+                .as_ref()
+                .map(|i| i.to_string())
+                .unwrap_or_else(|| i.to_string());
+            let ty = &f.ty;
+            let optional = is_maybe_segment(f);
+            quote! {
+                yew_nested_router::target::ParameterSchema {
+                    name: #name.into(),
+                    type_name: std::any::type_name::<#ty>().into(),
+                    optional: #optional,
                 }
             }
-            Fields::Unnamed(fields) => parse_rules(
-                &v,
-                true,
-                &fields.unnamed,
-                |_, cap| from_str(&cap),
-                |_, target| quote!(#target),
-                |name, values, target| quote!(Some(Self::#name(#(#values, )* #target))),
-            ),
-            Fields::Named(fields) => parse_rules(
-                &v,
-                false,
-                &fields.named,
-                |name, cap| {
-                    let name = name.expect("Must have a name");
-                    let from = from_str(&cap);
-                    quote!(#name: #from)
-                },
-                |name, target| {
-                    let name = name.expect("Must have a name");
-                    quote!(#name: #target)
-                },
-                |name, values, target| quote!(Some(Self::#name { #(#values, )* #target})),
-            ),
+        });
+
+        let nested = match nested {
+            Some(nested) => {
+                let t = &nested.ty;
+                quote! { Some(<#t as yew_nested_router::target::Target>::schema()) }
+            }
+            None => quote! { None },
+        };
+
+        quote_spanned! { v.span() =>
+            yew_nested_router::target::VariantSchema {
+                name: #name.into(),
+                segment: #segment.into(),
+                aliases: vec![#(#aliases.into()),*],
+                index: #index,
+                parameters: vec![#(#parameters),*],
+                nested: #nested,
+            }
+        }
+    })
+}
+
+/// Order the variants for parsing, variants with a higher priority come first. Otherwise, the
+/// declaration order is kept.
+fn parse_order(data: &DataEnum) -> Vec<(&Variant, Opts)> {
+    let mut variants = data
+        .variants
+        .iter()
+        .map(|v| (v, Opts::from_variant(v).expect("Unable to parse options")))
+        .collect::<Vec<_>>();
+    variants.sort_by_key(|(_, opts)| std::cmp::Reverse(opts.priority));
+    variants
+}
+
+/// Warn about variants sharing the same path prefix, having the same priority. For those, only
+/// the declaration order decides which one will be tried first. This includes the aliases of the
+/// variants.
+fn priority_conflicts(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
+    let variants = parse_order(data);
+
+    let mut conflicts = vec![];
+    for (i, (first, first_opts)) in variants.iter().enumerate() {
+        for (second, second_opts) in &variants[i + 1..] {
+            if first_opts.priority != second_opts.priority {
+                continue;
+            }
+            let second_discs = discriminators(second, second_opts);
+            let disc = match discriminators(first, first_opts)
+                .into_iter()
+                .find(|disc| second_discs.contains(disc))
+            {
+                Some(disc) => disc,
+                None => continue,
+            };
+
+            let name = format_ident!("same_priority_{}_{}", first.ident, second.ident);
+            let note = format!(
+                "Variants '{}' and '{}' share the path prefix '{}' and have the same priority (#[target(priority = N)]), the one declared first will be tried first",
+                first.ident, second.ident, disc
+            );
+            conflicts.push(quote_spanned! { second.span() =>
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    #[allow(non_camel_case_types)]
+                    struct #name;
+                    let _ = #name;
+                };
+            });
+        }
+    }
+
+    conflicts.into_iter()
+}
+
+/// Get the path segments, which parse into the variant: its own, followed by its aliases.
+///
+/// Aliases are only used for parsing, the variant always renders its own path segment.
+fn discriminators(variant: &Variant, opts: &Opts) -> Vec<String> {
+    let aliases = opts.alias.iter().map(|alias| {
+        let alias = alias.strip_prefix('/').unwrap_or(alias);
+        if alias.contains('/') {
+            panic!(
+                "An alias must be a single path segment: {} (on variant '{}')",
+                alias, variant.ident
+            );
         }
+        alias.to_string()
+    });
+
+    std::iter::once(to_discriminator(variant, opts))
+        .chain(aliases)
+        .collect()
+}
+
+/// parsing the path, into a target
+fn parse_path(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
+    parse_order(data).into_iter().flat_map(|(v, opts)| {
+        let subroutes = opts.subroutes.is_some();
+        discriminators(v, &opts)
+            .into_iter()
+            .map(move |disc| parse_variant(v, disc, subroutes))
     })
 }
 
+/// parsing the path into a variant, starting with the provided path segment
+fn parse_variant(v: &Variant, value: String, subroutes: bool) -> TokenStream {
+    let name = &v.ident;
+
+    match &v.fields {
+        Fields::Unit if subroutes => {
+            quote_spanned! { v.span() =>
+                [#value] => Some(Self::#name),
+                [#value, __internal_rest @ ..] if Self::#name.accepts_subroute(__internal_rest) => Some(Self::#name)
+            }
+        }
+        Fields::Unit => {
+            quote_spanned! { v.span() =>
+                [#value] => Some(Self::#name)
+            }
+        }
+        Fields::Unnamed(fields) => parse_rules(
+            v,
+            &value,
+            true,
+            &fields.unnamed,
+            |_, cap| from_str(&cap),
+            |_, target| quote!(#target),
+            |name, values, target| quote!(Some(Self::#name(#(#values, )* #target))),
+        ),
+        Fields::Named(fields) => parse_rules(
+            v,
+            &value,
+            false,
+            &fields.named,
+            |name, cap| {
+                let name = name.expect("Must have a name");
+                let from = from_str(&cap);
+                quote!(#name: #from)
+            },
+            |name, target| {
+                let name = name.expect("Must have a name");
+                quote!(#name: #target)
+            },
+            |name, values, target| quote!(Some(Self::#name { #(#values, )* #target})),
+        ),
+    }
+}
+
 fn parse_rules<P, F1, F2, F3>(
     v: &Variant,
+    disc: &str,
     expect_target: bool,
     fields: &Punctuated<Field, P>,
     converter: F1,
@@ -285,9 +733,6 @@ where
 
     let (values, nested) = nested_field(expect_target, fields);
 
-    let opts = Opts::from_variant(v).expect("Unable to parse options");
-    let disc = to_discriminator(&v, &opts);
-
     let (captures, values): (Vec<_>, Vec<_>) = values
         .iter()
         .enumerate()
@@ -352,8 +797,31 @@ where
             }
         }
         None => {
+            // an optional trailing segment may be absent
+            let absent = match fields.last() {
+                Some(last) if is_maybe_segment(last) => {
+                    let n = values.len() - 1;
+                    let absent = target_converter(
+                        last.ident.as_ref(),
+                        quote!(yew_nested_router::target::MaybeSegment::Absent),
+                    );
+                    let values = values[..n]
+                        .iter()
+                        .cloned()
+                        .chain(Some(absent))
+                        .collect::<Vec<_>>();
+                    let init = ctor(name, &values, quote!());
+                    let captures = &captures[..n];
+                    quote! {
+                        [#disc, #(#captures),*] => #init,
+                    }
+                }
+                _ => quote! {},
+            };
+
             let init = ctor(name, &values, quote!());
             quote_spanned! { v.span() =>
+                #absent
                 [#disc, #(#captures),*] => #init
             }
         }
@@ -477,6 +945,7 @@ fn predicates(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
         match &v.fields {
             Fields::Unit => quote_spanned! { v.span() =>
                 #[allow(unused)]
+                #[allow(clippy::wrong_self_convention)]
                 pub fn #fn_name(self) -> bool {
                     matches!(self, Self::#name)
                 }
@@ -485,6 +954,7 @@ fn predicates(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
                 let captures = fields.unnamed.iter().map(|_| quote! {_});
                 quote_spanned! { v.span() =>
                     #[allow(unused)]
+                    #[allow(clippy::wrong_self_convention)]
                     pub fn #fn_name(self) -> bool {
                         matches!(self, Self::#name( #(#captures),* ))
                     }
@@ -515,11 +985,21 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
     let render_path = render_path(&data);
     let render_self = render_self(&data);
+    let captures = captures(&data);
+    let is_index = is_index(&data);
+    let estimated_load_time_ms = estimated_load_time_ms(&data);
+    let schema = schema(&data);
+    let accepts_subroute = accepts_subroute(&data);
+    let with_captures = with_captures(&data);
+    let children = children(&data);
     let parse_path = parse_path(&data);
     let mappers = mappers(&data);
     let predicates = predicates(&data);
+    let priority_conflicts = priority_conflicts(&data);
 
     let output = quote! {
+        #(#priority_conflicts)*
+
         impl yew_nested_router::target::Target for #ident {
 
                 fn render_self_into(&self, __internal_path: &mut Vec<String>) {
@@ -534,7 +1014,46 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                     }
                 }
 
+                fn captures_into(&self, __internal_captures: &mut std::collections::HashMap<String, String>) {
+                    match self {
+                        #(#captures ,)*
+                    }
+                }
+
+                fn with_captures(&self, __internal_captures: &std::collections::HashMap<String, String>) -> Option<Self> {
+                    match self {
+                        #(#with_captures ,)*
+                    }
+                }
+
+                fn children() -> Vec<Self> {
+                    let mut __internal_children = vec![];
+                    #(#children)*
+                    __internal_children
+                }
+
+                fn is_index(&self) -> bool {
+                    #is_index
+                }
+
+                fn estimated_load_time_ms(&self) -> u32 {
+                    #estimated_load_time_ms
+                }
+
+                #[allow(unused)]
+                fn accepts_subroute(&self, __internal_remaining: &[&str]) -> bool {
+                    #accepts_subroute
+                }
+
+                fn schema() -> yew_nested_router::target::RouteSchema {
+                    yew_nested_router::target::RouteSchema {
+                        variants: vec![#(#schema),*],
+                    }
+                }
+
                 fn parse_path(__internal_path: &[&str]) -> Option<Self> {
+                    // variants with a higher priority may shadow others
+                    #[allow(unreachable_patterns)]
                     match __internal_path {
                         #(#parse_path ,)*
                         _ => None,